@@ -1,7 +1,10 @@
 use proc_macro::TokenStream;
-use proc_macro2::{Literal, Span, TokenTree};
-use quote::{format_ident, quote};
-use syn::{DeriveInput, GenericParam, Generics, Lifetime, parse_macro_input};
+use proc_macro2::{Ident, Literal, Span, TokenStream as TokenStream2, TokenTree};
+use quote::{ToTokens, format_ident, quote, quote_spanned};
+use syn::{
+    Attribute, DataEnum, DeriveInput, GenericArgument, GenericParam, Generics, Lifetime,
+    PathArguments, Type, ext::IdentExt, parse_macro_input, spanned::Spanned,
+};
 
 fn inject_static_bounds(input: &mut Generics) {
     for param in &mut input.params {
@@ -14,13 +17,143 @@ fn inject_static_bounds(input: &mut Generics) {
     }
 }
 
+/// Collects the items accessed by a query field and whether they are accessed mutably,
+/// for `&T`, `&mut T`, `Option<&T>`, `Option<&mut T>`, `All<&T>`, `All<&mut T>` and tuples of them.
+///
+/// Elements of tuples are named `{name}.{index}`.
+fn accessed_items<'t>(ty: &'t Type, name: String, out: &mut Vec<Access<'t>>) {
+    match ty {
+        Type::Reference(r) => out.push(Access {
+            name,
+            field: ty,
+            item: &r.elem,
+            mutable: r.mutability.is_some(),
+        }),
+        Type::Paren(p) => accessed_items(&p.elem, name, out),
+        Type::Group(g) => accessed_items(&g.elem, name, out),
+        Type::Tuple(t) => {
+            for (index, elem) in t.elems.iter().enumerate() {
+                accessed_items(elem, format!("{name}.{index}"), out);
+            }
+        }
+        Type::Path(p) if p.qself.is_none() => {
+            let Some(segment) = p.path.segments.last() else {
+                return;
+            };
+            if segment.ident != "Option" && segment.ident != "All" {
+                return;
+            }
+            let PathArguments::AngleBracketed(args) = &segment.arguments else {
+                return;
+            };
+            if let Some(GenericArgument::Type(Type::Reference(r))) = args.args.first() {
+                out.push(Access {
+                    name,
+                    field: ty,
+                    item: &r.elem,
+                    mutable: r.mutability.is_some(),
+                });
+            }
+        }
+        _ => (),
+    }
+}
+
+/// An item accessed by a query field.
+struct Access<'t> {
+    name: String,
+    field: &'t Type,
+    item: &'t Type,
+    mutable: bool,
+}
+
+/// Returns true if a type does not mention `Self`, generic parameters or non-`'static` lifetimes.
+fn is_concrete(tokens: TokenStream2, params: &[Ident]) -> bool {
+    let mut lifetime = false;
+    for tt in tokens {
+        match tt {
+            TokenTree::Group(group) => {
+                if !is_concrete(group.stream(), params) {
+                    return false;
+                }
+                lifetime = false;
+            }
+            TokenTree::Punct(punct) => lifetime = punct.as_char() == '\'',
+            TokenTree::Ident(ident) => {
+                if (lifetime && ident != "static") || ident == "Self" || params.contains(&ident) {
+                    return false;
+                }
+                lifetime = false;
+            }
+            TokenTree::Literal(_) => lifetime = false,
+        }
+    }
+    true
+}
+
 /// Make the type as a whole a `MetaTuple`, equivalent to wrapping it in `MetaItem`.
 #[proc_macro_derive(MetaItem)]
 pub fn derive_meta_item(tokens: TokenStream) -> TokenStream {
     let mut input: DeriveInput = parse_macro_input!(tokens as DeriveInput);
-    let name = input.ident;
     inject_static_bounds(&mut input.generics);
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    meta_item(&input.ident, &input.generics).into()
+}
+
+/// How a field of a derived `MetaTuple` is exposed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FieldKind {
+    Item,
+    Flatten,
+    Skip,
+}
+
+/// Parse `#[meta(skip)]` or `#[meta(flatten)]` on a field.
+fn field_kind(attrs: &[Attribute]) -> syn::Result<FieldKind> {
+    let mut kind = FieldKind::Item;
+    for attr in attrs {
+        if !attr.path().is_ident("meta") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            let next = if meta.path.is_ident("skip") {
+                FieldKind::Skip
+            } else if meta.path.is_ident("flatten") {
+                FieldKind::Flatten
+            } else {
+                return Err(meta.error("Expected `skip` or `flatten`."));
+            };
+            if kind != FieldKind::Item && kind != next {
+                return Err(meta.error("`skip` and `flatten` cannot be combined."));
+            }
+            kind = next;
+            Ok(())
+        })?;
+    }
+    Ok(kind)
+}
+
+/// Returns true if the struct is marked `#[meta(item)]`.
+fn is_item(attrs: &[Attribute]) -> syn::Result<bool> {
+    let mut item = false;
+    for attr in attrs {
+        if !attr.path().is_ident("meta") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("item") {
+                item = true;
+                Ok(())
+            } else {
+                Err(meta.error("Expected `item`."))
+            }
+        })?;
+    }
+    Ok(item)
+}
+
+/// Implementation of `#[derive(MetaItem)]`, also used by `#[meta(item)]`.
+fn meta_item(name: &Ident, generics: &Generics) -> TokenStream2 {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     quote! {
         unsafe impl #impl_generics ::meta_tuple::MetaAny for #name #ty_generics #where_clause {
             fn as_erased(&self) -> ::meta_tuple::ErasedInner<'_> {
@@ -29,9 +162,25 @@ pub fn derive_meta_item(tokens: TokenStream) -> TokenStream {
             fn as_erased_mut(&mut self) -> ::meta_tuple::ErasedInnerMut<'_> {
                 ::meta_tuple::ErasedInnerMut::Any(self)
             }
-            fn as_erased_ptr(&self) -> ::meta_tuple::ErasedInnerPtr<'_> {
-                ::meta_tuple::ErasedInnerPtr::Any(self)
+            unsafe fn as_erased_ptr(&self, this: *mut ()) -> ::meta_tuple::ErasedInnerPtr<'_> {
+                unsafe { Self::erase_ptr(this.cast()) }
+            }
+            unsafe fn erase_ptr<'__t>(this: *mut Self) -> ::meta_tuple::ErasedInnerPtr<'__t> where Self: '__t {
+                ::meta_tuple::ErasedInnerPtr::Any(this.cast(), ::core::any::TypeId::of::<Self>())
+            }
+            fn static_type_id() -> Option<::core::any::TypeId> {
+                Some(::core::any::TypeId::of::<Self>())
             }
+            fn meta_type_id(&self) -> Option<::core::any::TypeId> {
+                Some(::core::any::TypeId::of::<Self>())
+            }
+            fn visit(&self, f: &mut dyn FnMut(&dyn ::core::any::Any, &'static str)) {
+                f(self, ::core::any::type_name::<Self>())
+            }
+            fn visit_mut(&mut self, f: &mut dyn FnMut(&mut dyn ::core::any::Any, &'static str)) {
+                f(self, ::core::any::type_name::<Self>())
+            }
+            ::meta_tuple::__impl_into_sink_boxed!();
         }
 
         unsafe impl #impl_generics ::meta_tuple::MetaTuple for #name #ty_generics #where_clause {
@@ -41,44 +190,340 @@ pub fn derive_meta_item(tokens: TokenStream) -> TokenStream {
             fn get_mut<__T: 'static>(&mut self) -> Option<&mut __T> {
                 (self as &mut dyn ::core::any::Any).downcast_mut()
             }
-            fn get_mut_ptr<__T: 'static>(&self) -> Option<*mut __T> {
-                (self as &dyn ::core::any::Any).downcast_ref()
-                    .map(|x| x as *const __T as *mut __T)
+            unsafe fn get_mut_ptr<__T: 'static>(this: *mut Self) -> Option<*mut __T> {
+                (::core::any::TypeId::of::<__T>() == ::core::any::TypeId::of::<Self>()).then_some(this.cast())
+            }
+            fn get_all<__T: 'static>(&self) -> impl Iterator<Item = &__T> {
+                (self as &dyn ::core::any::Any).downcast_ref().into_iter()
+            }
+            fn get_all_mut<__T: 'static>(&mut self) -> impl Iterator<Item = &mut __T> {
+                (self as &mut dyn ::core::any::Any).downcast_mut().into_iter()
+            }
+            fn into_sink(self, sink: &mut impl ::meta_tuple::MetaSink) {
+                sink.push(self)
+            }
+        }
+
+        impl #impl_generics ::meta_tuple::Select<Self, ::meta_tuple::Here> for #name #ty_generics #where_clause {
+            fn select(&self) -> &Self {
+                self
+            }
+        }
+
+        impl #impl_generics ::meta_tuple::SelectMut<Self, ::meta_tuple::Here> for #name #ty_generics #where_clause {
+            fn select_mut(&mut self) -> &mut Self {
+                self
+            }
+        }
+
+        impl #impl_generics ::meta_tuple::Take<Self, ::meta_tuple::Here> for #name #ty_generics #where_clause {
+            type Rest = ();
+
+            fn take_item(self) -> (Self, ()) {
+                (self, ())
+            }
+        }
+    }
+}
+
+/// Implementation of `#[meta(item)]` with fields that are not skipped,
+/// exposes the type as an item before its fields, all read-only since they overlap.
+///
+/// `get` returns early if a field is found, `get_all` chains after the item and `visit` visits fields.
+fn meta_item_with_fields(
+    name: &Ident,
+    generics: &Generics,
+    get: TokenStream2,
+    get_all: TokenStream2,
+    visit: TokenStream2,
+) -> TokenStream2 {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        unsafe impl #impl_generics ::meta_tuple::MetaAny for #name #ty_generics #where_clause {
+            fn as_erased(&self) -> ::meta_tuple::ErasedInner<'_> {
+                ::meta_tuple::ErasedInner::Joined(
+                    ::meta_tuple::MetaItem::from_ref(self),
+                    ::meta_tuple::BundleFields::from_ref(self),
+                )
+            }
+            fn as_erased_mut(&mut self) -> ::meta_tuple::ErasedInnerMut<'_> {
+                ::meta_tuple::ErasedInnerMut::None
+            }
+            unsafe fn as_erased_ptr(&self, this: *mut ()) -> ::meta_tuple::ErasedInnerPtr<'_> {
+                ::meta_tuple::ErasedInnerPtr::None
+            }
+            unsafe fn erase_ptr<'__t>(this: *mut Self) -> ::meta_tuple::ErasedInnerPtr<'__t> where Self: '__t {
+                ::meta_tuple::ErasedInnerPtr::None
+            }
+            fn static_type_id() -> Option<::core::any::TypeId> {
+                Some(::core::any::TypeId::of::<Self>())
+            }
+            fn meta_type_id(&self) -> Option<::core::any::TypeId> {
+                Some(::core::any::TypeId::of::<Self>())
+            }
+            #[allow(unused_variables)]
+            fn visit(&self, f: &mut dyn FnMut(&dyn ::core::any::Any, &'static str)) {
+                f(self, ::core::any::type_name::<Self>());
+                #visit
+            }
+            fn visit_mut(&mut self, _: &mut dyn FnMut(&mut dyn ::core::any::Any, &'static str)) {}
+            ::meta_tuple::__impl_into_sink_boxed!();
+        }
+
+        #[allow(unused_variables)]
+        unsafe impl #impl_generics ::meta_tuple::MetaTuple for #name #ty_generics #where_clause {
+            fn get<__T: 'static>(&self) -> Option<&__T> {
+                if let Some(result) = (self as &dyn ::core::any::Any).downcast_ref() {
+                    return Some(result);
+                }
+                #get
+                None
+            }
+            fn get_mut<__T: 'static>(&mut self) -> Option<&mut __T> {
+                None
+            }
+            unsafe fn get_mut_ptr<__T: 'static>(this: *mut Self) -> Option<*mut __T> {
+                None
+            }
+            fn get_all<__T: 'static>(&self) -> impl Iterator<Item = &__T> {
+                (self as &dyn ::core::any::Any).downcast_ref().into_iter().chain(#get_all)
+            }
+            fn get_all_mut<__T: 'static>(&mut self) -> impl Iterator<Item = &mut __T> {
+                ::core::iter::empty()
+            }
+            fn into_sink(self, sink: &mut impl ::meta_tuple::MetaSink) {
+                sink.push(self)
+            }
+        }
+
+        impl #impl_generics ::meta_tuple::Select<Self, ::meta_tuple::Here> for #name #ty_generics #where_clause {
+            fn select(&self) -> &Self {
+                self
+            }
+        }
+
+        impl #impl_generics ::meta_tuple::Take<Self, ::meta_tuple::Here> for #name #ty_generics #where_clause {
+            type Rest = ();
+
+            fn take_item(self) -> (Self, ()) {
+                (self, ())
             }
         }
     }
-    .into()
 }
 
 /// Make the type a `MetaTuple` of its fields.
-#[proc_macro_derive(MetaTuple)]
+///
+/// On enums, only fields of the active variant are exposed.
+///
+/// # Attributes
+///
+/// * `#[meta(skip)]` on a field hides the field, `Take` drops skipped fields instead of moving them into `Rest`.
+/// * `#[meta(flatten)]` on a field searches the field as a nested `MetaTuple` in place,
+///   also through `MetaBundle::get_nested` when erased, not supported on enums.
+/// * `#[meta(item)]` on the type exposes the type as an item before its fields that are not skipped.
+///   Since the type overlaps its fields, if any field is exposed, the type and its fields
+///   are read-only and only the type can be taken.
+#[proc_macro_derive(MetaTuple, attributes(meta))]
 pub fn derive_meta_tuple(tokens: TokenStream) -> TokenStream {
     let mut input: DeriveInput = parse_macro_input!(tokens as DeriveInput);
     let name = input.ident;
     inject_static_bounds(&mut input.generics);
+    let item = match is_item(&input.attrs) {
+        Ok(item) => item,
+        Err(err) => return err.to_compile_error().into(),
+    };
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
     let data_struct = match input.data {
         syn::Data::Struct(data_struct) => data_struct,
-        _ => return quote! {compile_error!("Expected struct.")}.into(),
+        syn::Data::Enum(data_enum) => {
+            return meta_tuple_enum(&name, &input.generics, item, data_enum).into();
+        }
+        _ => return quote! {compile_error!("Expected struct or enum.")}.into(),
+    };
+    let members: Vec<_> = match data_struct.fields {
+        syn::Fields::Named(fields_named) => fields_named
+            .named
+            .into_iter()
+            .map(|field| {
+                let ident = field.ident.clone().unwrap();
+                (ident.unraw().to_string(), TokenTree::Ident(ident), field)
+            })
+            .collect(),
+        syn::Fields::Unnamed(fields_unnamed) => fields_unnamed
+            .unnamed
+            .into_iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let member = TokenTree::Literal(Literal::usize_unsuffixed(index));
+                (index.to_string(), member, field)
+            })
+            .collect(),
+        syn::Fields::Unit => Vec::new(),
     };
     let mut fields = Vec::new();
-    match data_struct.fields {
-        syn::Fields::Named(fields_named) => {
-            for field in fields_named.named {
-                fields.push(TokenTree::Ident(field.ident.unwrap()));
+    let mut names = Vec::new();
+    let mut types = Vec::new();
+    let mut flatten = Vec::new();
+    for (field_name, member, field) in members {
+        let kind = match field_kind(&field.attrs) {
+            Ok(kind) => kind,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        if kind == FieldKind::Skip {
+            continue;
+        }
+        names.push(field_name);
+        fields.push(member);
+        types.push(field.ty);
+        flatten.push(kind == FieldKind::Flatten);
+    }
+    if item && fields.is_empty() {
+        return meta_item(&name, &input.generics).into();
+    }
+
+    let indices: Vec<_> = (0..fields.len()).collect();
+    let len = fields.len();
+
+    let mut rest_types = Vec::new();
+    let mut rest_values = Vec::new();
+    for idx in 0..fields.len() {
+        let mut rest = None;
+        for (i, (field, field_ty)) in fields.iter().zip(&types).enumerate() {
+            if i == idx {
+                continue;
             }
+            let (item_ty, item_value) = if flatten[i] {
+                (quote! {#field_ty}, quote! {self.#field})
+            } else {
+                (
+                    quote! {::meta_tuple::MetaItem<#field_ty>},
+                    quote! {::meta_tuple::MetaItem(self.#field)},
+                )
+            };
+            rest = Some(match rest {
+                None => (item_ty, item_value),
+                Some((ty, value)) => (
+                    quote! {::meta_tuple::Join<#ty, #item_ty>},
+                    quote! {::meta_tuple::Join(#value, #item_value)},
+                ),
+            });
         }
-        syn::Fields::Unnamed(fields_unnamed) => {
-            for (index, _) in fields_unnamed.unnamed.into_iter().enumerate() {
-                fields.push(TokenTree::Literal(Literal::usize_unsuffixed(index)));
+        let (ty, value) = rest.unwrap_or_else(|| (quote! {()}, quote! {()}));
+        rest_types.push(ty);
+        rest_values.push(value);
+    }
+
+    let mut get = Vec::new();
+    let mut get_mut = Vec::new();
+    let mut get_mut_ptr = Vec::new();
+    let mut get_all = Vec::new();
+    let mut get_all_mut = Vec::new();
+    let mut into_sink = Vec::new();
+    let mut visit = Vec::new();
+    let mut visit_mut = Vec::new();
+    let mut nested = Vec::new();
+    let mut nested_mut = Vec::new();
+    let mut nested_ptr = Vec::new();
+    let mut selectors = Vec::new();
+    for (idx, ((field, ty), flatten)) in fields.iter().zip(&types).zip(&flatten).enumerate() {
+        if *flatten {
+            get.push(quote! {::meta_tuple::MetaTuple::get::<__T>(&self.#field)});
+            get_mut.push(quote! {::meta_tuple::MetaTuple::get_mut::<__T>(&mut self.#field)});
+            get_mut_ptr.push(quote! {unsafe {
+                <#ty as ::meta_tuple::MetaTuple>::get_mut_ptr::<__T>(&raw mut (*this).#field)
+            }});
+            get_all.push(quote! {::meta_tuple::MetaTuple::get_all::<__T>(&self.#field)});
+            get_all_mut
+                .push(quote! {::meta_tuple::MetaTuple::get_all_mut::<__T>(&mut self.#field)});
+            into_sink.push(quote! {::meta_tuple::MetaTuple::into_sink(self.#field, sink)});
+            visit.push(quote! {::meta_tuple::MetaAny::visit(&self.#field, f)});
+            visit_mut.push(quote! {::meta_tuple::MetaAny::visit_mut(&mut self.#field, f)});
+            nested.push(quote! {#idx => Some(&self.#field)});
+            nested_mut.push(quote! {#idx => Some(&mut self.#field)});
+            nested_ptr.push(quote! {
+                #idx => Some(unsafe { ::meta_tuple::ErasedPtr::new(&raw mut (*this).#field) })
+            });
+        } else {
+            let rest_ty = &rest_types[idx];
+            let rest_value = &rest_values[idx];
+            get.push(quote! {(&self.#field as &dyn ::core::any::Any).downcast_ref::<__T>()});
+            get_mut.push(
+                quote! {(&mut self.#field as &mut dyn ::core::any::Any).downcast_mut::<__T>()},
+            );
+            get_mut_ptr.push(quote! {
+                (::core::any::TypeId::of::<__T>() == ::core::any::TypeId::of::<#ty>())
+                    .then(|| unsafe { &raw mut (*this).#field }.cast())
+            });
+            get_all.push(quote! {(&self.#field as &dyn ::core::any::Any).downcast_ref::<__T>()});
+            get_all_mut.push(
+                quote! {(&mut self.#field as &mut dyn ::core::any::Any).downcast_mut::<__T>()},
+            );
+            into_sink.push(quote! {sink.push(self.#field)});
+            visit.push(quote! {f(&self.#field, ::core::any::type_name::<#ty>())});
+            visit_mut.push(quote! {f(&mut self.#field, ::core::any::type_name::<#ty>())});
+            selectors.push(quote! {
+                impl #impl_generics ::meta_tuple::Select<#ty, ::meta_tuple::Field<#idx>> for #name #ty_generics #where_clause {
+                    fn select(&self) -> &#ty {
+                        &self.#field
+                    }
+                }
+            });
+            if item {
+                continue;
             }
+            selectors.push(quote! {
+                impl #impl_generics ::meta_tuple::SelectMut<#ty, ::meta_tuple::Field<#idx>> for #name #ty_generics #where_clause {
+                    fn select_mut(&mut self) -> &mut #ty {
+                        &mut self.#field
+                    }
+                }
+
+                impl #impl_generics ::meta_tuple::Take<#ty, ::meta_tuple::Field<#idx>> for #name #ty_generics #where_clause {
+                    type Rest = #rest_ty;
+
+                    fn take_item(self) -> (#ty, Self::Rest) {
+                        (self.#field, #rest_value)
+                    }
+                }
+            });
         }
-        syn::Fields::Unit => (),
     }
 
-    let indices: Vec<_> = (0..fields.len()).collect();
+    // Flattened fields are nested `MetaAny`s searched in place of the field.
+    let (new_bundle_ptr, nested) = if nested.is_empty() {
+        (quote! {new}, None)
+    } else {
+        let nested = quote! {
+            fn has_nested(&self) -> bool {
+                true
+            }
 
-    quote! {
+            fn get_nested(&self, index: usize) -> Option<&dyn ::meta_tuple::MetaAny> {
+                match index {
+                    #(#nested,)*
+                    _ => None,
+                }
+            }
+
+            fn get_nested_mut(&mut self, index: usize) -> Option<&mut dyn ::meta_tuple::MetaAny> {
+                match index {
+                    #(#nested_mut,)*
+                    _ => None,
+                }
+            }
+
+            unsafe fn get_nested_ptr<'__t>(this: *mut Self, index: usize) -> Option<::meta_tuple::ErasedPtr<'__t>> where Self: '__t {
+                match index {
+                    #(#nested_ptr,)*
+                    _ => None,
+                }
+            }
+        };
+        (quote! {new_nested}, Some(nested))
+    };
+
+    let bundle = quote! {
         impl #impl_generics ::meta_tuple::MetaBundle for #name #ty_generics #where_clause {
             fn get_field(&self, index: usize) -> Option<&dyn ::core::any::Any> {
                 match index {
@@ -93,7 +538,78 @@ pub fn derive_meta_tuple(tokens: TokenStream) -> TokenStream {
                     _ => None,
                 }
             }
+
+            #[allow(unused_variables)]
+            unsafe fn get_field_ptr(this: *mut Self, index: usize) -> Option<(*mut (), ::core::any::TypeId)> {
+                match index {
+                    #(#indices => Some((unsafe { &raw mut (*this).#fields }.cast(), ::core::any::TypeId::of::<#types>())),)*
+                    _ => None,
+                }
+            }
+
+            fn field_count(&self) -> usize {
+                #len
+            }
+
+            fn field_name(&self, index: usize) -> Option<&'static str> {
+                match index {
+                    #(#indices => Some(#names),)*
+                    _ => None,
+                }
+            }
+
+            fn field_type_name(&self, index: usize) -> Option<&'static str> {
+                match index {
+                    #(#indices => Some(::core::any::type_name::<#types>()),)*
+                    _ => None,
+                }
+            }
+
+            fn field_type_id(&self, index: usize) -> Option<::core::any::TypeId> {
+                match index {
+                    #(#indices => Some(::core::any::TypeId::of::<#types>()),)*
+                    _ => None,
+                }
+            }
+
+            fn get_field_by_name(&self, name: &str) -> Option<&dyn ::core::any::Any> {
+                match name {
+                    #(#names => Some(&self.#fields),)*
+                    _ => None,
+                }
+            }
+
+            fn get_field_by_name_mut(&mut self, name: &str) -> Option<&mut dyn ::core::any::Any> {
+                match name {
+                    #(#names => Some(&mut self.#fields),)*
+                    _ => None,
+                }
+            }
+
+            #nested
+        }
+    };
+
+    if item {
+        let tuple = meta_item_with_fields(
+            &name,
+            &input.generics,
+            quote! {#(if let Some(result) = #get {
+                return Some(result);
+            })*},
+            quote! {::core::iter::empty() #(.chain(#get_all))*},
+            quote! {#(#visit;)*},
+        );
+        return quote! {
+            #bundle
+            #tuple
+            #(#selectors)*
         }
+        .into();
+    }
+
+    quote! {
+        #bundle
 
         unsafe impl #impl_generics ::meta_tuple::MetaAny for #name #ty_generics #where_clause {
             fn as_erased(&self) -> ::meta_tuple::ErasedInner<'_> {
@@ -104,39 +620,424 @@ pub fn derive_meta_tuple(tokens: TokenStream) -> TokenStream {
                 ::meta_tuple::ErasedInnerMut::Struct(self)
             }
 
-            fn as_erased_ptr(&self) -> ::meta_tuple::ErasedInnerPtr<'_> {
-                ::meta_tuple::ErasedInnerPtr::Struct(self)
+            unsafe fn as_erased_ptr(&self, this: *mut ()) -> ::meta_tuple::ErasedInnerPtr<'_> {
+                unsafe { Self::erase_ptr(this.cast()) }
+            }
+
+            unsafe fn erase_ptr<'__t>(this: *mut Self) -> ::meta_tuple::ErasedInnerPtr<'__t> where Self: '__t {
+                ::meta_tuple::ErasedInnerPtr::Struct(unsafe { ::meta_tuple::ErasedBundlePtr::#new_bundle_ptr(this) })
+            }
+
+            fn static_type_id() -> Option<::core::any::TypeId> {
+                Some(::core::any::TypeId::of::<Self>())
+            }
+
+            fn meta_type_id(&self) -> Option<::core::any::TypeId> {
+                Some(::core::any::TypeId::of::<Self>())
+            }
+
+            #[allow(unused_variables)]
+            fn visit(&self, f: &mut dyn FnMut(&dyn ::core::any::Any, &'static str)) {
+                #(#visit;)*
+            }
+
+            #[allow(unused_variables)]
+            fn visit_mut(&mut self, f: &mut dyn FnMut(&mut dyn ::core::any::Any, &'static str)) {
+                #(#visit_mut;)*
             }
+            ::meta_tuple::__impl_into_sink_boxed!();
         }
 
         unsafe impl #impl_generics ::meta_tuple::MetaTuple for #name #ty_generics #where_clause {
             fn get<__T: 'static>(&self) -> Option<&__T> {
-                #(if let Some(result) = (&self.#fields as &dyn ::core::any::Any).downcast_ref() {
+                #(if let Some(result) = #get {
                     return Some(result);
                 })*
                 None
             }
             fn get_mut<__T: 'static>(&mut self) -> Option<&mut __T> {
-                #(if let Some(result) = (&mut self.#fields as &mut dyn ::core::any::Any).downcast_mut() {
+                #(if let Some(result) = #get_mut {
                     return Some(result);
                 })*
                 None
             }
-            fn get_mut_ptr<__T: 'static>(&self) -> Option<*mut __T> {
-                #(if let Some(result) = (&self.#fields as &dyn ::core::any::Any).downcast_ref() {
-                    return Some(result as *const __T as *mut __T);
+            #[allow(unused_variables)]
+            unsafe fn get_mut_ptr<__T: 'static>(this: *mut Self) -> Option<*mut __T> {
+                #(if let Some(result) = #get_mut_ptr {
+                    return Some(result);
                 })*
                 None
             }
+            fn get_all<__T: 'static>(&self) -> impl Iterator<Item = &__T> {
+                ::core::iter::empty() #(.chain(#get_all))*
+            }
+            fn get_all_mut<__T: 'static>(&mut self) -> impl Iterator<Item = &mut __T> {
+                ::core::iter::empty() #(.chain(#get_all_mut))*
+            }
+            #[allow(unused_variables)]
+            fn into_sink(self, sink: &mut impl ::meta_tuple::MetaSink) {
+                #(#into_sink;)*
+            }
         }
+
+        #(#selectors)*
     }.into()
 }
 
+/// `#[derive(MetaTuple)]` on enums, exposes fields of the active variant.
+fn meta_tuple_enum(
+    name: &Ident,
+    generics: &Generics,
+    item: bool,
+    data_enum: DataEnum,
+) -> TokenStream2 {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    if data_enum.variants.is_empty() {
+        return quote! {compile_error!("Expected at least one variant.");};
+    }
+    let mut patterns = Vec::new();
+    let mut ref_patterns = Vec::new();
+    let mut variants = Vec::new();
+    for variant in data_enum.variants {
+        let ident = variant.ident;
+        let mut bindings = Vec::new();
+        let mut names = Vec::new();
+        let mut types = Vec::new();
+        let mut elements = Vec::new();
+        let mut ref_elements = Vec::new();
+        let named = matches!(variant.fields, syn::Fields::Named(_));
+        for (index, field) in variant.fields.into_iter().enumerate() {
+            let kind = match field_kind(&field.attrs) {
+                Ok(kind) => kind,
+                Err(err) => return err.to_compile_error(),
+            };
+            if kind == FieldKind::Flatten {
+                return syn::Error::new(
+                    field.span(),
+                    "`#[meta(flatten)]` is not supported on enums.",
+                )
+                .to_compile_error();
+            }
+            if kind == FieldKind::Skip {
+                if !named {
+                    elements.push(quote! {_});
+                    ref_elements.push(quote! {_});
+                }
+                continue;
+            }
+            let binding = format_ident!("__f{}", index);
+            match &field.ident {
+                Some(field_ident) => {
+                    names.push(field_ident.unraw().to_string());
+                    elements.push(quote! {#field_ident: #binding});
+                    ref_elements.push(quote! {#field_ident: ref #binding});
+                }
+                None => {
+                    names.push(index.to_string());
+                    elements.push(quote! {#binding});
+                    ref_elements.push(quote! {ref #binding});
+                }
+            }
+            bindings.push(binding);
+            types.push(field.ty);
+        }
+        patterns.push(if named {
+            quote! {Self::#ident { #(#elements,)* .. }}
+        } else if elements.is_empty() {
+            quote! {Self::#ident { .. }}
+        } else {
+            quote! {Self::#ident(#(#elements),*)}
+        });
+        // Matched on the place `*this`, only the discriminant and the exposed fields are borrowed.
+        ref_patterns.push(if named {
+            quote! {Self::#ident { #(#ref_elements,)* .. }}
+        } else if ref_elements.is_empty() {
+            quote! {Self::#ident { .. }}
+        } else {
+            quote! {Self::#ident(#(#ref_elements),*)}
+        });
+        variants.push((bindings, names, types));
+    }
+    if item && variants.iter().all(|(bindings, ..)| bindings.is_empty()) {
+        return meta_item(name, generics);
+    }
+
+    let max = variants
+        .iter()
+        .map(|(bindings, ..)| bindings.len())
+        .max()
+        .unwrap_or(0);
+    let mut get_field = Vec::new();
+    let mut get_field_mut = Vec::new();
+    let mut get_field_ptr = Vec::new();
+    let mut field_count = Vec::new();
+    let mut field_name = Vec::new();
+    let mut field_type_name = Vec::new();
+    let mut field_type_id = Vec::new();
+    let mut get_field_by_name = Vec::new();
+    let mut visit = Vec::new();
+    let mut get = Vec::new();
+    let mut get_mut = Vec::new();
+    let mut get_mut_ptr = Vec::new();
+    let mut get_all = Vec::new();
+    let mut get_all_mut = Vec::new();
+    let mut into_sink = Vec::new();
+    for (bindings, names, types) in &variants {
+        let indices: Vec<_> = (0..bindings.len()).collect();
+        let len = bindings.len();
+        let padding = (len..max).map(|_| quote! {None});
+        let padding_mut = padding.clone();
+        get_field.push(quote! {
+            match index {
+                #(#indices => Some(#bindings),)*
+                _ => None,
+            }
+        });
+        get_field_mut.push(quote! {
+            match index {
+                #(#indices => Some(#bindings),)*
+                _ => None,
+            }
+        });
+        get_field_ptr.push(quote! {
+            match index {
+                #(#indices => Some((::meta_tuple::project(this, #bindings).cast(), ::core::any::TypeId::of::<#types>())),)*
+                _ => None,
+            }
+        });
+        field_count.push(quote! {#len});
+        field_name.push(quote! {
+            match index {
+                #(#indices => Some(#names),)*
+                _ => None,
+            }
+        });
+        field_type_name.push(quote! {
+            match index {
+                #(#indices => Some(::core::any::type_name::<#types>()),)*
+                _ => None,
+            }
+        });
+        field_type_id.push(quote! {
+            match index {
+                #(#indices => Some(::core::any::TypeId::of::<#types>()),)*
+                _ => None,
+            }
+        });
+        get_field_by_name.push(quote! {
+            match name {
+                #(#names => Some(#bindings),)*
+                _ => None,
+            }
+        });
+        visit.push(quote! {
+            #(f(#bindings, ::core::any::type_name::<#types>());)*
+        });
+        get.push(quote! {
+            #(if let Some(result) = (#bindings as &dyn ::core::any::Any).downcast_ref() {
+                return Some(result);
+            })*
+        });
+        get_mut.push(quote! {
+            #(if let Some(result) = (#bindings as &mut dyn ::core::any::Any).downcast_mut() {
+                return Some(result);
+            })*
+        });
+        get_mut_ptr.push(quote! {
+            #(if ::core::any::TypeId::of::<__T>() == ::core::any::TypeId::of::<#types>() {
+                return Some(::meta_tuple::project(this, #bindings).cast());
+            })*
+        });
+        get_all.push(quote! {
+            [#((#bindings as &dyn ::core::any::Any).downcast_ref(),)* #(#padding,)*]
+        });
+        get_all_mut.push(quote! {
+            [#((#bindings as &mut dyn ::core::any::Any).downcast_mut(),)* #(#padding_mut,)*]
+        });
+        into_sink.push(quote! {
+            #(sink.push(#bindings);)*
+        });
+    }
+
+    let bundle = quote! {
+        #[allow(unused_variables)]
+        impl #impl_generics ::meta_tuple::MetaBundle for #name #ty_generics #where_clause {
+            fn get_field(&self, index: usize) -> Option<&dyn ::core::any::Any> {
+                match self {
+                    #(#patterns => #get_field,)*
+                }
+            }
+
+            fn get_field_mut(&mut self, index: usize) -> Option<&mut dyn ::core::any::Any> {
+                match self {
+                    #(#patterns => #get_field_mut,)*
+                }
+            }
+
+            unsafe fn get_field_ptr(this: *mut Self, index: usize) -> Option<(*mut (), ::core::any::TypeId)> {
+                unsafe {
+                    match *this {
+                        #(#ref_patterns => #get_field_ptr,)*
+                    }
+                }
+            }
+
+            fn field_count(&self) -> usize {
+                match self {
+                    #(#patterns => #field_count,)*
+                }
+            }
+
+            fn field_name(&self, index: usize) -> Option<&'static str> {
+                match self {
+                    #(#patterns => #field_name,)*
+                }
+            }
+
+            fn field_type_name(&self, index: usize) -> Option<&'static str> {
+                match self {
+                    #(#patterns => #field_type_name,)*
+                }
+            }
+
+            fn field_type_id(&self, index: usize) -> Option<::core::any::TypeId> {
+                match self {
+                    #(#patterns => #field_type_id,)*
+                }
+            }
+
+            fn get_field_by_name(&self, name: &str) -> Option<&dyn ::core::any::Any> {
+                match self {
+                    #(#patterns => #get_field_by_name,)*
+                }
+            }
+
+            fn get_field_by_name_mut(&mut self, name: &str) -> Option<&mut dyn ::core::any::Any> {
+                match self {
+                    #(#patterns => #get_field_by_name,)*
+                }
+            }
+        }
+    };
+
+    if item {
+        let tuple = meta_item_with_fields(
+            name,
+            generics,
+            quote! {
+                match self {
+                    #(#patterns => {#get})*
+                }
+            },
+            quote! {{
+                let items: [Option<&__T>; #max] = match self {
+                    #(#patterns => #get_all,)*
+                };
+                items.into_iter().flatten()
+            }},
+            quote! {
+                match self {
+                    #(#patterns => {#visit})*
+                }
+            },
+        );
+        return quote! {
+            #bundle
+            #tuple
+        };
+    }
+
+    quote! {
+        #bundle
+
+        #[allow(unused_variables)]
+        unsafe impl #impl_generics ::meta_tuple::MetaAny for #name #ty_generics #where_clause {
+            fn as_erased(&self) -> ::meta_tuple::ErasedInner<'_> {
+                ::meta_tuple::ErasedInner::Struct(self)
+            }
+
+            fn as_erased_mut(&mut self) -> ::meta_tuple::ErasedInnerMut<'_> {
+                ::meta_tuple::ErasedInnerMut::Struct(self)
+            }
+
+            unsafe fn as_erased_ptr(&self, this: *mut ()) -> ::meta_tuple::ErasedInnerPtr<'_> {
+                unsafe { Self::erase_ptr(this.cast()) }
+            }
+
+            unsafe fn erase_ptr<'__t>(this: *mut Self) -> ::meta_tuple::ErasedInnerPtr<'__t> where Self: '__t {
+                ::meta_tuple::ErasedInnerPtr::Struct(unsafe { ::meta_tuple::ErasedBundlePtr::new(this) })
+            }
+
+            fn static_type_id() -> Option<::core::any::TypeId> {
+                Some(::core::any::TypeId::of::<Self>())
+            }
+
+            fn meta_type_id(&self) -> Option<::core::any::TypeId> {
+                Some(::core::any::TypeId::of::<Self>())
+            }
+
+            fn visit(&self, f: &mut dyn FnMut(&dyn ::core::any::Any, &'static str)) {
+                match self {
+                    #(#patterns => {#visit})*
+                }
+            }
+
+            fn visit_mut(&mut self, f: &mut dyn FnMut(&mut dyn ::core::any::Any, &'static str)) {
+                match self {
+                    #(#patterns => {#visit})*
+                }
+            }
+            ::meta_tuple::__impl_into_sink_boxed!();
+        }
+
+        #[allow(unused_variables)]
+        unsafe impl #impl_generics ::meta_tuple::MetaTuple for #name #ty_generics #where_clause {
+            fn get<__T: 'static>(&self) -> Option<&__T> {
+                match self {
+                    #(#patterns => {#get})*
+                }
+                None
+            }
+            fn get_mut<__T: 'static>(&mut self) -> Option<&mut __T> {
+                match self {
+                    #(#patterns => {#get_mut})*
+                }
+                None
+            }
+            unsafe fn get_mut_ptr<__T: 'static>(this: *mut Self) -> Option<*mut __T> {
+                unsafe {
+                    match *this {
+                        #(#ref_patterns => {#get_mut_ptr})*
+                    }
+                }
+                None
+            }
+            fn get_all<__T: 'static>(&self) -> impl Iterator<Item = &__T> {
+                let items: [Option<&__T>; #max] = match self {
+                    #(#patterns => #get_all,)*
+                };
+                items.into_iter().flatten()
+            }
+            fn get_all_mut<__T: 'static>(&mut self) -> impl Iterator<Item = &mut __T> {
+                let items: [Option<&mut __T>; #max] = match self {
+                    #(#patterns => #get_all_mut,)*
+                };
+                items.into_iter().flatten()
+            }
+            fn into_sink(self, sink: &mut impl ::meta_tuple::MetaSink) {
+                match self {
+                    #(#patterns => {#into_sink})*
+                }
+            }
+        }
+    }
+}
+
 /// Fetch individual items from a `MetaTuple`.
 ///
 /// # Syntax
 ///
-/// ```
+/// ```ignore
 /// #[derive(MetaQuery)]
 /// pub struct MyQuery<'t> {
 ///     int: &'t i32,
@@ -147,6 +1048,14 @@ pub fn derive_meta_tuple(tokens: TokenStream) -> TokenStream {
 /// # Semantics
 ///
 /// Requires a generic lifetime, all generic types will be added `+ 'static` bound.
+///
+/// Fields that access the same item where at least one of them is mutable,
+/// like `&'t i32` and `&'t mut i32`, are rejected at compile time,
+/// including elements of tuple fields like `(&'t mut i32, &'t i32)`.
+/// Fields that depend on generic parameters are checked at runtime instead.
+///
+/// On enums, variants are tried in order and the first variant whose fields
+/// are all found is returned, fields of different variants may alias.
 #[proc_macro_derive(MetaQuery)]
 pub fn derive_meta_query(tokens: TokenStream) -> TokenStream {
     let mut input: DeriveInput = parse_macro_input!(tokens as DeriveInput);
@@ -159,106 +1068,323 @@ pub fn derive_meta_query(tokens: TokenStream) -> TokenStream {
     }
     let gat_param = g2.split_for_impl().1;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
-    let data_struct = match input.data {
-        syn::Data::Struct(data_struct) => data_struct,
-        _ => return quote! {compile_error!("Expected struct.")}.into(),
-    };
-    let mut fields = Vec::new();
-    let mut types = Vec::new();
-    let mut is_tuple = false;
-    match data_struct.fields {
-        syn::Fields::Named(fields_named) => {
-            for field in fields_named.named {
-                fields.push(field.ident.unwrap());
-                types.push(field.ty);
+    let (variants, is_enum): (Vec<_>, _) = match input.data {
+        syn::Data::Struct(data_struct) => (
+            vec![query_variant(
+                quote! {#name},
+                String::new(),
+                data_struct.fields,
+            )],
+            false,
+        ),
+        syn::Data::Enum(data_enum) => {
+            if data_enum.variants.is_empty() {
+                return quote! {compile_error!("Expected at least one variant.")}.into();
             }
+            let variants = data_enum
+                .variants
+                .into_iter()
+                .map(|variant| {
+                    let ident = variant.ident;
+                    query_variant(quote! {#name::#ident}, format!("{ident}::"), variant.fields)
+                })
+                .collect();
+            (variants, true)
         }
-        syn::Fields::Unnamed(fields_unnamed) => {
-            for (index, field) in fields_unnamed.unnamed.into_iter().enumerate() {
-                fields.push(format_ident!("__v{}", index));
-                types.push(field.ty);
-                is_tuple = true;
+        _ => return quote! {compile_error!("Expected struct or enum.")}.into(),
+    };
+    let params: Vec<Ident> = input
+        .generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(p) => Some(p.ident.clone()),
+            GenericParam::Const(p) => Some(p.ident.clone()),
+            GenericParam::Lifetime(_) => None,
+        })
+        .collect();
+
+    let mut errors: Option<syn::Error> = None;
+    let mut distinct = Vec::new();
+
+    for variant in &variants {
+        let QueryVariant {
+            prefix,
+            fields,
+            types,
+            is_tuple,
+            ..
+        } = variant;
+        let mut accesses = Vec::new();
+        for (index, ty) in types.iter().enumerate() {
+            let name = match is_tuple {
+                true => format!("{prefix}{index}"),
+                false => format!("{prefix}{}", fields[index]),
+            };
+            accessed_items(ty, name, &mut accesses);
+        }
+        for x in 0..accesses.len() {
+            for y in x + 1..accesses.len() {
+                let (a, b) = (&accesses[x], &accesses[y]);
+                if !a.mutable && !b.mutable {
+                    continue;
+                }
+                let a_str = a.item.to_token_stream().to_string();
+                if a_str == b.item.to_token_stream().to_string() {
+                    let error = syn::Error::new_spanned(
+                        b.field,
+                        format!(
+                            "`{a_str}` is accessed by both `{}` and `{}`, and at least one of them is mutable.",
+                            a.name, b.name,
+                        ),
+                    );
+                    match &mut errors {
+                        Some(errors) => errors.combine(error),
+                        None => errors = Some(error),
+                    }
+                } else if is_concrete(a.item.to_token_stream(), &params)
+                    && is_concrete(b.item.to_token_stream(), &params)
+                {
+                    // Catches type aliases, the conflicting impl error names the duplicated type.
+                    let index = Literal::usize_unsuffixed(distinct.len());
+                    let (a_item, b_item) = (a.item, b.item);
+                    let a_impl = quote_spanned! {a.field.span()=>
+                        impl MutableFieldsMustNotAlias<#index> for Item<#a_item> {}
+                    };
+                    let b_impl = quote_spanned! {b.field.span()=>
+                        impl MutableFieldsMustNotAlias<#index> for Item<#b_item> {}
+                    };
+                    distinct.push(quote! {#a_impl #b_impl});
+                }
             }
         }
-        syn::Fields::Unit => (),
     }
 
-    let mut type_comparisons = Vec::new();
+    if let Some(errors) = errors {
+        return errors.to_compile_error().into();
+    }
+
+    let distinct = (!distinct.is_empty()).then(|| {
+        quote! {
+            #[allow(dead_code)]
+            const _: () = {
+                struct Item<T: ?Sized>(::core::marker::PhantomData<T>);
+                trait MutableFieldsMustNotAlias<const N: usize> {}
+                #(#distinct)*
+            };
+        }
+    });
 
-    for x in 0..types.len() {
-        for y in x + 1..types.len() {
+    // Only fields of the same variant coexist.
+    let mut aliased = Vec::new();
+
+    for QueryVariant { types, .. } in &variants {
+        for x in 0..types.len() {
             let a = &types[x];
-            let b = &types[y];
-            type_comparisons.push(quote! {
-                <#a as ::meta_tuple::MetaQuerySingle>::compatible::<#b>()
+            aliased.push(quote! {
+                .or_else(<#a as ::meta_tuple::MetaQuery>::aliased)
             });
+            for b in &types[x + 1..] {
+                aliased.push(quote! {
+                    .or_else(::meta_tuple::conflict::<#a, #b>)
+                });
+            }
         }
     }
 
-    if type_comparisons.is_empty() {
-        type_comparisons.push(quote! {true});
+    let mut ref_init = Vec::new();
+    let mut dyn_init = Vec::new();
+    let mut ptr_init = Vec::new();
+    let mut ptr_types = Vec::new();
+    let mut mut_ptr = Vec::new();
+    let mut dyn_mut_ptr = Vec::new();
+    let mut all_types = Vec::new();
+    for variant in &variants {
+        let QueryVariant {
+            path,
+            fields,
+            types,
+            is_tuple,
+            ..
+        } = variant;
+        let init = |expr: TokenStream2| {
+            if *is_tuple {
+                quote! {#path(
+                    #(<#types as ::meta_tuple::MetaQuery>::#expr,)*
+                )}
+            } else {
+                quote! {#path {
+                    #(#fields: <#types as ::meta_tuple::MetaQuery>::#expr,)*
+                }}
+            }
+        };
+        ref_init.push(init(quote! {query_ref(input)?}));
+        dyn_init.push(init(quote! {query_dyn_ref(input)?}));
+        ptr_init.push(if *is_tuple {
+            quote! {#path(
+                #(unsafe {<#types as ::meta_tuple::MetaQuery>::from_ptr(#fields)},)*
+            )}
+        } else {
+            quote! {#path {
+                #(#fields: unsafe {<#types as ::meta_tuple::MetaQuery>::from_ptr(#fields)},)*
+            }}
+        });
+        ptr_types.push(quote! {(#(<#types as ::meta_tuple::MetaQuery>::OutputPtr<'__t>,)*)});
+        mut_ptr.push(quote! {
+            (#(unsafe { <#types as ::meta_tuple::MetaQuery>::query_mut_ptr(input) }?,)*)
+        });
+        dyn_mut_ptr.push(quote! {
+            (#(unsafe { <#types as ::meta_tuple::MetaQuery>::query_dyn_mut_ptr(input) }?,)*)
+        });
+        all_types.extend(types);
     }
 
-    let ref_init = if is_tuple {
-        quote! {(
-            #(<#types as ::meta_tuple::MetaQuery>::query_ref(input)?,)*
-        )}
-    } else {
-        quote! {{
-            #(#fields: <#types as ::meta_tuple::MetaQuery>::query_ref(input)?,)*
-        }}
-    };
+    let query = if is_enum {
+        // Each variant is a slot in `OutputPtr`, only the matched variant is `Some`.
+        let slots: Vec<_> = (0..variants.len())
+            .map(|index| format_ident!("__variant{}", index))
+            .collect();
+        let wrap = |ptrs: &[TokenStream2]| -> Vec<TokenStream2> {
+            ptrs.iter()
+                .enumerate()
+                .map(|(index, ptr)| {
+                    let slots = (0..variants.len()).map(|slot| match slot == index {
+                        true => quote! {Some(#ptr)},
+                        false => quote! {None},
+                    });
+                    quote! {(#(#slots,)*)}
+                })
+                .collect()
+        };
+        let mut_ptr = wrap(&mut_ptr);
+        let dyn_mut_ptr = wrap(&dyn_mut_ptr);
+        let bindings = variants.iter().map(|variant| {
+            let fields = &variant.fields;
+            quote! {(#(#fields,)*)}
+        });
+        quote! {
+            fn query_ref<'__t, T: MetaTuple + ?Sized + '__t>(input: &'__t T) -> Option<Self::Output<'__t>> {
+                None #(.or_else(|| Some(#ref_init)))*
+            }
 
-    let dyn_init = if is_tuple {
-        quote! {(
-            #(<#types as ::meta_tuple::MetaQuery>::query_dyn_ref(input)?,)*
-        )}
-    } else {
-        quote! {{
-            #(#fields: <#types as ::meta_tuple::MetaQuery>::query_dyn_ref(input)?,)*
-        }}
-    };
+            fn query_dyn_ref<'__t>(input: &'__t dyn ::meta_tuple::MetaAny) -> Option<Self::Output<'__t>> {
+                None #(.or_else(|| Some(#dyn_init)))*
+            }
 
-    let ptr_init = if is_tuple {
-        quote! {(
-            #(unsafe {<#types as ::meta_tuple::MetaQuery>::from_ptr(#fields)},)*
-        )}
-    } else {
-        quote! {{
-            #(#fields: unsafe {<#types as ::meta_tuple::MetaQuery>::from_ptr(#fields)},)*
-        }}
-    };
+            type OutputPtr<'__t> = (#(Option<#ptr_types>,)*);
 
-    quote! {
-        unsafe impl #impl_generics ::meta_tuple::MetaQuery for #name #ty_generics #where_clause {
-            type Output<'__t> = #name #gat_param;
+            unsafe fn from_ptr<'__t>(ptr: Self::OutputPtr<'__t>) -> Self::Output<'__t> {
+                let (#(#slots,)*) = ptr;
+                #(if let Some(#bindings) = #slots {
+                    return #ptr_init;
+                })*
+                ::core::unreachable!()
+            }
+
+            unsafe fn query_mut_ptr<'__t, T: MetaTuple + ?Sized + '__t>(input: *mut T) -> Option<Self::OutputPtr<'__t>> {
+                None #(.or_else(|| Some(#mut_ptr)))*
+            }
 
+            unsafe fn query_dyn_mut_ptr<'__t>(input: *mut (dyn ::meta_tuple::MetaAny + '__t)) -> Option<Self::OutputPtr<'__t>> {
+                None #(.or_else(|| Some(#dyn_mut_ptr)))*
+            }
+        }
+    } else {
+        let fields = &variants[0].fields;
+        quote! {
             fn query_ref<'__t, T: MetaTuple + ?Sized + '__t>(input: &'__t T) -> Option<Self::Output<'__t>> {
-                Some(#name #ref_init)
+                Some(#(#ref_init)*)
             }
 
             fn query_dyn_ref<'__t>(input: &'__t dyn ::meta_tuple::MetaAny) -> Option<Self::Output<'__t>> {
-                Some(#name #dyn_init)
+                Some(#(#dyn_init)*)
             }
 
-            type OutputPtr<'__t> = (#(<#types as ::meta_tuple::MetaQuery>::OutputPtr<'__t>,)*);
+            type OutputPtr<'__t> = #(#ptr_types)*;
 
             unsafe fn from_ptr<'__t>(ptr: Self::OutputPtr<'__t>) -> Self::Output<'__t> {
                 let (#(#fields,)*) = ptr;
-                #name #ptr_init
+                #(#ptr_init)*
             }
 
-            fn query_mut_ptr<'__t, T: MetaTuple + ?Sized + '__t>(input: &'__t T) -> Option<Self::OutputPtr<'__t>> {
-                Some((#(<#types as ::meta_tuple::MetaQuery>::query_mut_ptr(input)?,)*))
+            unsafe fn query_mut_ptr<'__t, T: MetaTuple + ?Sized + '__t>(input: *mut T) -> Option<Self::OutputPtr<'__t>> {
+                Some(#(#mut_ptr)*)
             }
 
-            fn query_dyn_mut_ptr<'__t>(input: &'__t dyn ::meta_tuple::MetaAny) -> Option<Self::OutputPtr<'__t>> {
-                Some((#(<#types as ::meta_tuple::MetaQuery>::query_dyn_mut_ptr(input)?,)*))
+            unsafe fn query_dyn_mut_ptr<'__t>(input: *mut (dyn ::meta_tuple::MetaAny + '__t)) -> Option<Self::OutputPtr<'__t>> {
+                Some(#(#dyn_mut_ptr)*)
             }
+        }
+    };
+
+    quote! {
+        #distinct
+
+        unsafe impl #impl_generics ::meta_tuple::MetaQuery for #name #ty_generics #where_clause {
+            type Output<'__t> = #name #gat_param;
+
+            #query
 
             fn validate() -> bool {
-                #(#type_comparisons)&&*
+                <Self as ::meta_tuple::MetaQuery>::aliased().is_none()
+            }
+
+            #[allow(unused_variables)]
+            fn access(visit: &mut dyn FnMut(::meta_tuple::Access)) {
+                #(<#all_types as ::meta_tuple::MetaQuery>::access(visit);)*
+            }
+
+            fn aliased() -> Option<::meta_tuple::MetaError> {
+                None #(#aliased)*
+            }
+
+            fn report<T: MetaTuple + ?Sized>(input: &T, mutable: bool, report: &mut dyn FnMut(::meta_tuple::MetaError)) {
+                #(<#all_types as ::meta_tuple::MetaQuery>::report(input, mutable, report);)*
+            }
+
+            fn report_dyn(input: &dyn ::meta_tuple::MetaAny, mutable: bool, report: &mut dyn FnMut(::meta_tuple::MetaError)) {
+                #(<#all_types as ::meta_tuple::MetaQuery>::report_dyn(input, mutable, report);)*
             }
         }
     }.into()
 }
+
+/// Fields of a struct or an enum variant queried by `#[derive(MetaQuery)]`.
+struct QueryVariant {
+    /// Constructor, `Name` or `Name::Variant`.
+    path: TokenStream2,
+    /// Prepended to field names in errors.
+    prefix: String,
+    fields: Vec<Ident>,
+    types: Vec<Type>,
+    is_tuple: bool,
+}
+
+fn query_variant(path: TokenStream2, prefix: String, input: syn::Fields) -> QueryVariant {
+    let mut fields = Vec::new();
+    let mut types = Vec::new();
+    let mut is_tuple = false;
+    match input {
+        syn::Fields::Named(fields_named) => {
+            for field in fields_named.named {
+                fields.push(field.ident.unwrap());
+                types.push(field.ty);
+            }
+        }
+        syn::Fields::Unnamed(fields_unnamed) => {
+            for (index, field) in fields_unnamed.unnamed.into_iter().enumerate() {
+                fields.push(format_ident!("__v{}", index));
+                types.push(field.ty);
+                is_tuple = true;
+            }
+        }
+        syn::Fields::Unit => (),
+    }
+    QueryVariant {
+        path,
+        prefix,
+        fields,
+        types,
+        is_tuple,
+    }
+}
@@ -0,0 +1,238 @@
+use crate::{
+    DynMetaTuple, ErasedInner, ErasedInnerMut, ErasedInnerPtr, ErasedListPtr, ErasedPtr, MetaAny,
+    MetaList, MetaSink, MetaTuple,
+    dynamic::{erased_type_id, impl_meta_any_unsized},
+};
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    any::{Any, TypeId},
+    ptr,
+};
+
+unsafe impl<T: MetaAny> MetaAny for Box<T> {
+    fn as_erased(&self) -> ErasedInner<'_> {
+        MetaAny::as_erased(&**self)
+    }
+
+    fn as_erased_mut(&mut self) -> ErasedInnerMut<'_> {
+        MetaAny::as_erased_mut(&mut **self)
+    }
+
+    unsafe fn as_erased_ptr(&self, this: *mut ()) -> ErasedInnerPtr<'_> {
+        unsafe { Self::erase_ptr(this.cast()) }
+    }
+
+    unsafe fn erase_ptr<'t>(this: *mut Self) -> ErasedInnerPtr<'t>
+    where
+        Self: 't,
+    {
+        unsafe { T::erase_ptr(&raw mut **this) }
+    }
+
+    fn static_type_id() -> Option<TypeId> {
+        T::static_type_id()?;
+        Some(erased_type_id::<Self>())
+    }
+
+    fn meta_type_id(&self) -> Option<TypeId> {
+        Self::static_type_id()
+    }
+
+    fn visit(&self, f: &mut dyn FnMut(&dyn Any, &'static str)) {
+        MetaAny::visit(&**self, f)
+    }
+
+    fn visit_mut(&mut self, f: &mut dyn FnMut(&mut dyn Any, &'static str)) {
+        MetaAny::visit_mut(&mut **self, f)
+    }
+
+    fn into_sink_boxed(self: Box<Self>, sink: &mut dyn FnMut(Box<dyn Any>, &'static str)) {
+        T::into_sink_boxed(*self, sink)
+    }
+}
+
+impl_meta_any_unsized!(Box<DynMetaTuple>, Box<dyn MetaAny + '_>);
+
+unsafe impl<T: MetaTuple> MetaTuple for Box<T> {
+    fn get<U: 'static>(&self) -> Option<&U> {
+        MetaTuple::get(&**self)
+    }
+
+    fn get_mut<U: 'static>(&mut self) -> Option<&mut U> {
+        MetaTuple::get_mut(&mut **self)
+    }
+
+    unsafe fn get_mut_ptr<U: 'static>(this: *mut Self) -> Option<*mut U> {
+        unsafe { T::get_mut_ptr(&raw mut **this) }
+    }
+
+    fn get_all<U: 'static>(&self) -> impl Iterator<Item = &U> {
+        MetaTuple::get_all(&**self)
+    }
+
+    fn get_all_mut<U: 'static>(&mut self) -> impl Iterator<Item = &mut U> {
+        MetaTuple::get_all_mut(&mut **self)
+    }
+
+    fn into_sink(self, sink: &mut impl MetaSink) {
+        (*self).into_sink(sink)
+    }
+}
+
+/// Items are moved by `into_sink` as boxed values, see [`MetaSink::push_boxed`].
+unsafe impl MetaTuple for Box<DynMetaTuple> {
+    fn get<T: 'static>(&self) -> Option<&T> {
+        MetaTuple::get(&**self)
+    }
+
+    fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        MetaTuple::get_mut(&mut **self)
+    }
+
+    unsafe fn get_mut_ptr<T: 'static>(this: *mut Self) -> Option<*mut T> {
+        unsafe { DynMetaTuple::get_mut_ptr(&raw mut **this) }
+    }
+
+    fn get_all<T: 'static>(&self) -> impl Iterator<Item = &T> {
+        MetaTuple::get_all(&**self)
+    }
+
+    fn get_all_mut<T: 'static>(&mut self) -> impl Iterator<Item = &mut T> {
+        MetaTuple::get_all_mut(&mut **self)
+    }
+
+    fn into_sink(self, sink: &mut impl MetaSink) {
+        MetaAny::into_sink_boxed(self, &mut |item, type_name| sink.push_boxed(item, type_name))
+    }
+}
+
+/// Items are moved by `into_sink` as boxed values, see [`MetaSink::push_boxed`].
+unsafe impl MetaTuple for Box<dyn MetaAny + '_> {
+    fn get<T: 'static>(&self) -> Option<&T> {
+        (**self).get()
+    }
+
+    fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        (**self).get_mut()
+    }
+
+    unsafe fn get_mut_ptr<T: 'static>(this: *mut Self) -> Option<*mut T> {
+        unsafe { <dyn MetaAny>::get_ptr(&raw mut **this) }
+    }
+
+    fn get_all<T: 'static>(&self) -> impl Iterator<Item = &T> {
+        (**self).get_all()
+    }
+
+    fn get_all_mut<T: 'static>(&mut self) -> impl Iterator<Item = &mut T> {
+        (**self).get_all_mut()
+    }
+
+    fn into_sink(self, sink: &mut impl MetaSink) {
+        MetaAny::into_sink_boxed(self, &mut |item, type_name| sink.push_boxed(item, type_name))
+    }
+}
+
+impl DynMetaTuple {
+    /// Convert a boxed `dyn MetaAny` into a boxed [`DynMetaTuple`].
+    pub fn from_box(b: Box<dyn MetaAny>) -> Box<Self> {
+        // # Safety
+        //
+        // Safe since `#[repr(transparent)]`, same implementation as `ref_cast`.
+        unsafe { Box::from_raw(Box::into_raw(b) as *mut Self) }
+    }
+}
+
+/// Downcast a boxed [`MetaAny`] to its concrete type.
+///
+/// Implemented for boxed subtraits of [`MetaAny`] by [`impl_meta_any!`](crate::impl_meta_any).
+pub trait DowncastBox: Sized {
+    /// Downcast to the concrete type `M`, returns `self` if not matches.
+    fn downcast<M: MetaAny + 'static>(self) -> Result<Box<M>, Self>;
+}
+
+impl MetaList for Vec<Box<dyn MetaAny + '_>> {
+    fn get_item(&self, idx: usize) -> Option<&dyn MetaAny> {
+        self.as_slice().get(idx).map(|item| &**item as &dyn MetaAny)
+    }
+
+    fn get_item_mut(&mut self, idx: usize) -> Option<&mut dyn MetaAny> {
+        self.as_mut_slice()
+            .get_mut(idx)
+            .map(|item| &mut **item as &mut dyn MetaAny)
+    }
+
+    unsafe fn get_item_ptr<'t>(this: *mut Self, idx: usize) -> Option<ErasedPtr<'t>>
+    where
+        Self: 't,
+    {
+        let item = unsafe { &*this }.as_slice().get(idx)?;
+        Some(unsafe { ErasedPtr::new(ptr::from_ref(item).cast_mut()) })
+    }
+}
+
+unsafe impl MetaAny for Vec<Box<dyn MetaAny + '_>> {
+    fn as_erased(&self) -> ErasedInner<'_> {
+        ErasedInner::Many(self)
+    }
+
+    fn as_erased_mut(&mut self) -> ErasedInnerMut<'_> {
+        ErasedInnerMut::Many(self)
+    }
+
+    unsafe fn as_erased_ptr(&self, this: *mut ()) -> ErasedInnerPtr<'_> {
+        unsafe { Self::erase_ptr(this.cast()) }
+    }
+
+    unsafe fn erase_ptr<'t>(this: *mut Self) -> ErasedInnerPtr<'t>
+    where
+        Self: 't,
+    {
+        ErasedInnerPtr::Many(unsafe { ErasedListPtr::new(this) })
+    }
+
+    fn visit(&self, f: &mut dyn FnMut(&dyn Any, &'static str)) {
+        for item in self {
+            item.visit(f)
+        }
+    }
+
+    fn visit_mut(&mut self, f: &mut dyn FnMut(&mut dyn Any, &'static str)) {
+        for item in self {
+            item.visit_mut(f)
+        }
+    }
+
+    crate::__impl_into_sink_boxed!();
+}
+
+/// Items are moved by `into_sink` as boxed values, see [`MetaSink::push_boxed`].
+unsafe impl MetaTuple for Vec<Box<dyn MetaAny + '_>> {
+    fn get<T: 'static>(&self) -> Option<&T> {
+        self.iter().find_map(|item| item.get())
+    }
+
+    fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.iter_mut().find_map(|item| item.get_mut())
+    }
+
+    unsafe fn get_mut_ptr<T: 'static>(this: *mut Self) -> Option<*mut T> {
+        unsafe { &*this }
+            .iter()
+            .find_map(|item| unsafe { <dyn MetaAny>::get_ptr((&raw const **item).cast_mut()) })
+    }
+
+    fn get_all<T: 'static>(&self) -> impl Iterator<Item = &T> {
+        self.iter().flat_map(|item| item.get_all())
+    }
+
+    fn get_all_mut<T: 'static>(&mut self) -> impl Iterator<Item = &mut T> {
+        self.iter_mut().flat_map(|item| item.get_all_mut())
+    }
+
+    fn into_sink(self, sink: &mut impl MetaSink) {
+        for item in self {
+            MetaAny::into_sink_boxed(item, &mut |item, type_name| sink.push_boxed(item, type_name))
+        }
+    }
+}
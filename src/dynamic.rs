@@ -1,18 +1,164 @@
 use crate::{Join, MetaItem, MetaTuple};
-use core::{any::Any, mem, ptr::NonNull};
+use core::{
+    any::{Any, TypeId, type_name},
+    marker::PhantomData,
+    mem,
+};
+use polonius_the_crab::{polonius_loop, polonius_return};
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
 
 /// Utility trait for erasing structs.
 pub trait MetaBundle {
     fn get_field(&self, idx: usize) -> Option<&dyn Any>;
     fn get_field_mut(&mut self, idx: usize) -> Option<&mut dyn Any>;
+
+    /// Returns the number of fields.
+    fn field_count(&self) -> usize {
+        (0..)
+            .take_while(|idx| self.get_field(*idx).is_some())
+            .count()
+    }
+
+    /// Returns the name of a field, if named.
+    fn field_name(&self, _: usize) -> Option<&'static str> {
+        None
+    }
+
+    /// Returns the type name of a field, if known.
+    fn field_type_name(&self, _: usize) -> Option<&'static str> {
+        None
+    }
+
+    /// Returns the [`TypeId`] of a field, if exists.
+    fn field_type_id(&self, idx: usize) -> Option<TypeId> {
+        self.get_field(idx).map(Any::type_id)
+    }
+
+    /// Obtain a field by its name, if exists.
+    fn get_field_by_name(&self, name: &str) -> Option<&dyn Any> {
+        let idx = (0..self.field_count()).find(|idx| self.field_name(*idx) == Some(name))?;
+        self.get_field(idx)
+    }
+
+    /// Obtain a mutable field by its name, if exists.
+    fn get_field_by_name_mut(&mut self, name: &str) -> Option<&mut dyn Any> {
+        let idx = (0..self.field_count()).find(|idx| self.field_name(*idx) == Some(name))?;
+        self.get_field_mut(idx)
+    }
+
+    /// Obtain a field as a pointer derived from `this` and its [`TypeId`], if exists.
+    ///
+    /// # Safety
+    ///
+    /// `this` must be valid for reads, the result is valid for writes if `this` is.
+    unsafe fn get_field_ptr(this: *mut Self, idx: usize) -> Option<(*mut (), TypeId)>
+    where
+        Self: Sized;
+
+    /// Returns `true` if some fields are nested [`MetaAny`]s, see [`MetaBundle::get_nested`].
+    ///
+    /// By default returns `false`, then lookups skip to fields with [`MetaBundle::find_field`].
+    fn has_nested(&self) -> bool {
+        false
+    }
+
+    /// Obtain a field as a nested [`MetaAny`] searched in place of the field, if it is nested.
+    ///
+    /// By default no field is nested, [`MetaBundle::has_nested`] must return `true` if any is.
+    fn get_nested(&self, _: usize) -> Option<&dyn MetaAny> {
+        None
+    }
+
+    /// Obtain a field as a mutable nested [`MetaAny`], see [`MetaBundle::get_nested`].
+    fn get_nested_mut(&mut self, _: usize) -> Option<&mut dyn MetaAny> {
+        None
+    }
+
+    /// Obtain a nested field as a pointer derived from `this`, see [`MetaBundle::get_nested`].
+    ///
+    /// Only used if the bundle is erased by [`ErasedBundlePtr::new_nested`].
+    ///
+    /// # Safety
+    ///
+    /// `this` must be valid for reads for `'t`, the result is valid for writes if `this` is.
+    unsafe fn get_nested_ptr<'t>(this: *mut Self, idx: usize) -> Option<ErasedPtr<'t>>
+    where
+        Self: Sized + 't,
+    {
+        let _ = (this, idx);
+        None
+    }
+
+    /// Returns the index of the `n`th field of type `type_id`,
+    /// or the number of fields of that type if there are not enough.
+    ///
+    /// By default checks every field, override for faster lookups.
+    /// Not used if [`MetaBundle::has_nested`].
+    fn find_field(&self, type_id: TypeId, n: usize) -> Result<usize, usize> {
+        let mut count = 0;
+        for idx in 0.. {
+            let Some(field) = self.get_field(idx) else {
+                break;
+            };
+            if field.type_id() == type_id {
+                if count == n {
+                    return Ok(idx);
+                }
+                count += 1;
+            }
+        }
+        Err(count)
+    }
+
+    /// Same as [`MetaBundle::find_field`] without creating references.
+    ///
+    /// # Safety
+    ///
+    /// `this` must be valid for reads.
+    unsafe fn find_field_ptr(this: *mut Self, type_id: TypeId, n: usize) -> Result<usize, usize>
+    where
+        Self: Sized,
+    {
+        let mut count = 0;
+        for idx in 0.. {
+            let Some((_, field)) = (unsafe { Self::get_field_ptr(this, idx) }) else {
+                break;
+            };
+            if field == type_id {
+                if count == n {
+                    return Ok(idx);
+                }
+                count += 1;
+            }
+        }
+        Err(count)
+    }
+}
+
+/// Utility trait for erasing lists of [`MetaAny`], searched front to back.
+pub trait MetaList {
+    fn get_item(&self, idx: usize) -> Option<&dyn MetaAny>;
+    fn get_item_mut(&mut self, idx: usize) -> Option<&mut dyn MetaAny>;
+
+    /// Obtain an item as a pointer derived from `this`, if exists.
+    ///
+    /// # Safety
+    ///
+    /// `this` must be valid for reads for `'t`, the result is valid for writes if `this` is.
+    unsafe fn get_item_ptr<'t>(this: *mut Self, idx: usize) -> Option<ErasedPtr<'t>>
+    where
+        Self: Sized + 't;
 }
 
 /// Erased [`MetaTuple`].
+#[derive(Clone, Copy)]
 pub enum ErasedInner<'t> {
     None,
     Any(&'t dyn Any),
     Joined(&'t dyn MetaAny, &'t dyn MetaAny),
     Struct(&'t dyn MetaBundle),
+    Many(&'t dyn MetaList),
 }
 
 /// Erased mutable [`MetaTuple`].
@@ -21,14 +167,467 @@ pub enum ErasedInnerMut<'t> {
     Any(&'t mut dyn Any),
     Joined(&'t mut dyn MetaAny, &'t mut dyn MetaAny),
     Struct(&'t mut dyn MetaBundle),
+    Many(&'t mut dyn MetaList),
 }
 
 /// Erased [`MetaTuple`] that returns pointers.
+///
+/// Pointers are derived from a pointer to the [`MetaTuple`] without creating references,
+/// so pointers to different items do not invalidate each other.
+#[derive(Clone, Copy)]
 pub enum ErasedInnerPtr<'t> {
     None,
-    Any(&'t dyn Any),
-    Joined(&'t dyn MetaAny, &'t dyn MetaAny),
-    Struct(&'t dyn MetaBundle),
+    Any(*mut (), TypeId),
+    Joined(ErasedPtr<'t>, ErasedPtr<'t>),
+    Struct(ErasedBundlePtr<'t>),
+    Many(ErasedListPtr<'t>),
+}
+
+/// Pointer to a [`MetaAny`] that is erased on demand.
+#[derive(Clone, Copy)]
+pub struct ErasedPtr<'t> {
+    ptr: *mut (),
+    erase: unsafe fn(*mut ()) -> ErasedInnerPtr<'t>,
+}
+
+impl<'t> ErasedPtr<'t> {
+    /// # Safety
+    ///
+    /// `ptr` must be valid for reads for `'t`.
+    pub unsafe fn new<M: MetaAny + 't>(ptr: *mut M) -> Self {
+        unsafe fn erase<'t, M: MetaAny + 't>(ptr: *mut ()) -> ErasedInnerPtr<'t> {
+            unsafe { M::erase_ptr(ptr.cast()) }
+        }
+        ErasedPtr {
+            ptr: ptr.cast(),
+            erase: erase::<M>,
+        }
+    }
+
+    /// Erase the pointed [`MetaAny`].
+    pub fn erase(self) -> ErasedInnerPtr<'t> {
+        // Safety:
+        //
+        // Safe since `ptr` is valid for `'t`.
+        unsafe { (self.erase)(self.ptr) }
+    }
+}
+
+/// Pointer to a [`MetaBundle`] that obtains fields on demand.
+#[derive(Clone, Copy)]
+pub struct ErasedBundlePtr<'t> {
+    ptr: *mut (),
+    field: unsafe fn(*mut (), usize) -> Option<(*mut (), TypeId)>,
+    find: unsafe fn(*mut (), TypeId, usize) -> Result<usize, usize>,
+    nested: Option<unsafe fn(*mut (), usize) -> Option<ErasedPtr<'t>>>,
+    p: PhantomData<&'t ()>,
+}
+
+impl<'t> ErasedBundlePtr<'t> {
+    /// # Safety
+    ///
+    /// `ptr` must be valid for reads for `'t`.
+    pub unsafe fn new<B: MetaBundle + 't>(ptr: *mut B) -> Self {
+        unsafe fn field<B: MetaBundle>(ptr: *mut (), idx: usize) -> Option<(*mut (), TypeId)> {
+            unsafe { B::get_field_ptr(ptr.cast(), idx) }
+        }
+        unsafe fn find<B: MetaBundle>(
+            ptr: *mut (),
+            type_id: TypeId,
+            n: usize,
+        ) -> Result<usize, usize> {
+            unsafe { B::find_field_ptr(ptr.cast(), type_id, n) }
+        }
+        ErasedBundlePtr {
+            ptr: ptr.cast(),
+            field: field::<B>,
+            find: find::<B>,
+            nested: None,
+            p: PhantomData,
+        }
+    }
+
+    /// Same as [`ErasedBundlePtr::new`] for bundles with nested fields, see [`MetaBundle::get_nested_ptr`].
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be valid for reads for `'t`.
+    pub unsafe fn new_nested<B: MetaBundle + 't>(ptr: *mut B) -> Self {
+        unsafe fn nested<'t, B: MetaBundle + 't>(ptr: *mut (), idx: usize) -> Option<ErasedPtr<'t>> {
+            unsafe { B::get_nested_ptr(ptr.cast(), idx) }
+        }
+        ErasedBundlePtr {
+            nested: Some(nested::<B>),
+            ..unsafe { Self::new(ptr) }
+        }
+    }
+
+    /// Returns `true` if some fields are nested, see [`MetaBundle::has_nested`].
+    pub fn has_nested(self) -> bool {
+        self.nested.is_some()
+    }
+
+    /// Obtain a field as a nested [`MetaAny`] pointer, if it is nested.
+    pub fn get_nested(self, idx: usize) -> Option<ErasedPtr<'t>> {
+        // Safety:
+        //
+        // Safe since `ptr` is valid for `'t`.
+        unsafe { (self.nested?)(self.ptr, idx) }
+    }
+
+    /// Obtain a field as a pointer and its [`TypeId`], if exists.
+    pub fn get_field(self, idx: usize) -> Option<(*mut (), TypeId)> {
+        // Safety:
+        //
+        // Safe since `ptr` is valid for `'t`.
+        unsafe { (self.field)(self.ptr, idx) }
+    }
+
+    /// Returns the index of the `n`th field of type `type_id`,
+    /// or the number of fields of that type if there are not enough.
+    pub fn find_field(self, type_id: TypeId, n: usize) -> Result<usize, usize> {
+        // Safety:
+        //
+        // Safe since `ptr` is valid for `'t`.
+        unsafe { (self.find)(self.ptr, type_id, n) }
+    }
+}
+
+/// Pointer to a [`MetaList`] that obtains items on demand.
+#[derive(Clone, Copy)]
+pub struct ErasedListPtr<'t> {
+    ptr: *mut (),
+    item: unsafe fn(*mut (), usize) -> Option<ErasedPtr<'t>>,
+}
+
+impl<'t> ErasedListPtr<'t> {
+    /// # Safety
+    ///
+    /// `ptr` must be valid for reads for `'t`.
+    pub unsafe fn new<L: MetaList + 't>(ptr: *mut L) -> Self {
+        unsafe fn item<'t, L: MetaList + 't>(ptr: *mut (), idx: usize) -> Option<ErasedPtr<'t>> {
+            unsafe { L::get_item_ptr(ptr.cast(), idx) }
+        }
+        ErasedListPtr {
+            ptr: ptr.cast(),
+            item: item::<L>,
+        }
+    }
+
+    /// Obtain an item as a pointer, if exists.
+    pub fn get_item(self, idx: usize) -> Option<ErasedPtr<'t>> {
+        // Safety:
+        //
+        // Safe since `ptr` is valid for `'t`.
+        unsafe { (self.item)(self.ptr, idx) }
+    }
+}
+
+/// Project `this` to `field`, a reference into `*this`, keeping the provenance of `this`.
+///
+/// # Safety
+///
+/// `field` must point into `*this`.
+#[doc(hidden)]
+pub unsafe fn project<S: ?Sized, T>(this: *mut S, field: &T) -> *mut T {
+    let offset = core::ptr::from_ref(field).addr() - this.addr();
+    unsafe { this.cast::<u8>().add(offset).cast() }
+}
+
+/// Project `this` to its value without creating references, if `Some`.
+///
+/// # Safety
+///
+/// `this` must be valid for reads.
+pub(crate) unsafe fn project_some<T>(this: *mut Option<T>) -> Option<*mut T> {
+    // Only reads the discriminant, `is_none` would create a reference.
+    #[allow(clippy::redundant_pattern_matching)]
+    if unsafe { matches!(*this, None) } {
+        return None;
+    }
+    // `as_slice` points to where the value would be, even for `None`.
+    let none = None::<T>;
+    let offset = none.as_slice().as_ptr().addr() - (&raw const none).addr();
+    Some(unsafe { this.cast::<u8>().add(offset).cast() })
+}
+
+/// Returns the [`TypeId`] of `T` with lifetimes erased, same implementation as `typeid`.
+///
+/// Only equals a [`TypeId`] of a `'static` type if `T` itself is `'static`.
+pub(crate) fn erased_type_id<T: ?Sized>() -> TypeId {
+    trait NonStaticAny {
+        fn get_type_id(&self) -> TypeId
+        where
+            Self: 'static;
+    }
+
+    impl<T: ?Sized> NonStaticAny for PhantomData<T> {
+        fn get_type_id(&self) -> TypeId
+        where
+            Self: 'static,
+        {
+            TypeId::of::<T>()
+        }
+    }
+
+    let phantom = PhantomData::<T>;
+    // # Safety
+    //
+    // Only the lifetime of the trait object is extended, `self` is never used.
+    NonStaticAny::get_type_id(unsafe {
+        mem::transmute::<&dyn NonStaticAny, &(dyn NonStaticAny + 'static)>(&phantom)
+    })
+}
+
+/// Returns `item` if `n` reaches zero, otherwise decrements `n`.
+fn nth_match<T>(item: Option<T>, n: &mut usize) -> Option<T> {
+    let item = item?;
+    if *n == 0 {
+        Some(item)
+    } else {
+        *n -= 1;
+        None
+    }
+}
+
+impl<'t> ErasedInner<'t> {
+    /// Returns the number of items of type `T`.
+    pub fn count<T: 'static>(&self) -> usize {
+        match *self {
+            ErasedInner::None => 0,
+            ErasedInner::Any(any) => any.is::<T>() as usize,
+            ErasedInner::Joined(a, b) => a.as_erased().count::<T>() + b.as_erased().count::<T>(),
+            ErasedInner::Struct(s) => (0..s.field_count())
+                .map(|idx| match s.get_nested(idx) {
+                    Some(nested) => nested.as_erased().count::<T>(),
+                    None => s.get_field(idx).is_some_and(|field| field.is::<T>()) as usize,
+                })
+                .sum(),
+            ErasedInner::Many(list) => (0..)
+                .map_while(|idx| list.get_item(idx))
+                .map(|item| item.as_erased().count::<T>())
+                .sum(),
+        }
+    }
+
+    /// Obtain the `n`th item of type `T`, if exists.
+    pub fn get_nth<T: 'static>(&self, mut n: usize) -> Option<&'t T> {
+        self.nth_inner(&mut n)
+    }
+
+    /// Visit every item and its type name in lookup order, see [`MetaAny::visit`].
+    ///
+    /// Type names of items that are not fields of a [`MetaBundle`] or visited by a
+    /// nested [`MetaAny`] are unknown and reported as `"_"`.
+    pub fn visit(&self, f: &mut dyn FnMut(&dyn Any, &'static str)) {
+        match *self {
+            ErasedInner::None => (),
+            ErasedInner::Any(any) => f(any, "_"),
+            ErasedInner::Joined(a, b) => {
+                a.visit(f);
+                b.visit(f);
+            }
+            ErasedInner::Struct(s) => {
+                for idx in 0..s.field_count() {
+                    if let Some(nested) = s.get_nested(idx) {
+                        nested.visit(f)
+                    } else if let Some(field) = s.get_field(idx) {
+                        f(field, s.field_type_name(idx).unwrap_or("_"))
+                    }
+                }
+            }
+            ErasedInner::Many(list) => {
+                for item in (0..).map_while(|idx| list.get_item(idx)) {
+                    item.visit(f)
+                }
+            }
+        }
+    }
+
+    fn nth_inner<T: 'static>(&self, n: &mut usize) -> Option<&'t T> {
+        match *self {
+            ErasedInner::None => None,
+            ErasedInner::Any(any) => nth_match(any.downcast_ref(), n),
+            ErasedInner::Joined(a, b) => a
+                .as_erased()
+                .nth_inner(n)
+                .or_else(|| b.as_erased().nth_inner(n)),
+            ErasedInner::Struct(s) if s.has_nested() => (0..s.field_count()).find_map(|idx| {
+                match s.get_nested(idx) {
+                    Some(nested) => nested.as_erased().nth_inner(n),
+                    None => nth_match(s.get_field(idx)?.downcast_ref(), n),
+                }
+            }),
+            ErasedInner::Struct(s) => match s.find_field(TypeId::of::<T>(), *n) {
+                Ok(idx) => s.get_field(idx)?.downcast_ref(),
+                Err(count) => {
+                    *n -= count;
+                    None
+                }
+            },
+            ErasedInner::Many(list) => (0..)
+                .map_while(|idx| list.get_item(idx))
+                .find_map(|item| item.as_erased().nth_inner(n)),
+        }
+    }
+}
+
+impl<'t> ErasedInnerMut<'t> {
+    /// Visit every mutable item and its type name in lookup order, see [`MetaAny::visit_mut`].
+    ///
+    /// Type names are reported like [`ErasedInner::visit`].
+    pub fn visit_mut(self, f: &mut dyn FnMut(&mut dyn Any, &'static str)) {
+        match self {
+            ErasedInnerMut::None => (),
+            ErasedInnerMut::Any(any) => f(any, "_"),
+            ErasedInnerMut::Joined(a, b) => {
+                a.visit_mut(f);
+                b.visit_mut(f);
+            }
+            ErasedInnerMut::Struct(s) => {
+                for idx in 0..s.field_count() {
+                    let type_name = s.field_type_name(idx).unwrap_or("_");
+                    if s.get_nested(idx).is_some() {
+                        if let Some(nested) = s.get_nested_mut(idx) {
+                            nested.visit_mut(f)
+                        }
+                    } else if let Some(field) = s.get_field_mut(idx) {
+                        f(field, type_name)
+                    }
+                }
+            }
+            ErasedInnerMut::Many(list) => {
+                let mut idx = 0;
+                while let Some(item) = list.get_item_mut(idx) {
+                    item.visit_mut(f);
+                    idx += 1;
+                }
+            }
+        }
+    }
+
+    /// Obtain the `n`th item of type `T`, if exists.
+    pub fn get_nth<T: 'static>(self, mut n: usize) -> Option<&'t mut T> {
+        self.nth_inner(&mut n)
+    }
+
+    fn nth_inner<T: 'static>(self, n: &mut usize) -> Option<&'t mut T> {
+        match self {
+            ErasedInnerMut::None => None,
+            ErasedInnerMut::Any(any) => nth_match(any.downcast_mut(), n),
+            ErasedInnerMut::Joined(a, b) => match a.as_erased_mut().nth_inner(n) {
+                Some(result) => Some(result),
+                None => b.as_erased_mut().nth_inner(n),
+            },
+            ErasedInnerMut::Struct(mut s) if s.has_nested() => {
+                let len = s.field_count();
+                let mut idx = 0;
+                polonius_loop!(|s| -> Option<&'polonius mut T> {
+                    if idx == len {
+                        polonius_return!(None);
+                    }
+                    if s.get_nested(idx).is_some() {
+                        let nested = s.get_nested_mut(idx);
+                        if let Some(result) = nested.and_then(|nested| nested.as_erased_mut().nth_inner(n)) {
+                            polonius_return!(Some(result));
+                        }
+                    } else if s.field_type_id(idx) == Some(TypeId::of::<T>()) {
+                        if *n == 0 {
+                            polonius_return!(s.get_field_mut(idx).and_then(|field| field.downcast_mut()));
+                        }
+                        *n -= 1;
+                    }
+                    idx += 1;
+                })
+            }
+            ErasedInnerMut::Struct(s) => match s.find_field(TypeId::of::<T>(), *n) {
+                Ok(idx) => s.get_field_mut(idx)?.downcast_mut(),
+                Err(count) => {
+                    *n -= count;
+                    None
+                }
+            },
+            ErasedInnerMut::Many(mut list) => {
+                let mut idx = 0;
+                polonius_loop!(|list| -> Option<&'polonius mut T> {
+                    let Some(item) = list.get_item_mut(idx) else {
+                        polonius_return!(None);
+                    };
+                    if let Some(result) = item.as_erased_mut().nth_inner(n) {
+                        polonius_return!(Some(result));
+                    }
+                    idx += 1;
+                })
+            }
+        }
+    }
+}
+
+impl<'t> ErasedInnerPtr<'t> {
+    /// Obtain the `n`th item of type `T` as a pointer, if exists.
+    pub fn get_nth<T: 'static>(&self, mut n: usize) -> Option<*mut T> {
+        self.nth_inner(&mut n)
+    }
+
+    fn nth_inner<T: 'static>(&self, n: &mut usize) -> Option<*mut T> {
+        let cast = |(ptr, type_id): (*mut (), TypeId)| {
+            (type_id == TypeId::of::<T>()).then_some(ptr.cast::<T>())
+        };
+        match *self {
+            ErasedInnerPtr::None => None,
+            ErasedInnerPtr::Any(ptr, type_id) => nth_match(cast((ptr, type_id)), n),
+            ErasedInnerPtr::Joined(a, b) => a
+                .erase()
+                .nth_inner(n)
+                .or_else(|| b.erase().nth_inner(n)),
+            ErasedInnerPtr::Struct(s) if s.has_nested() => (0..)
+                .map_while(|idx| Some((idx, s.get_field(idx)?)))
+                .find_map(|(idx, field)| match s.get_nested(idx) {
+                    Some(nested) => nested.erase().nth_inner(n),
+                    None => nth_match(cast(field), n),
+                }),
+            ErasedInnerPtr::Struct(s) => match s.find_field(TypeId::of::<T>(), *n) {
+                Ok(idx) => cast(s.get_field(idx)?),
+                Err(count) => {
+                    *n -= count;
+                    None
+                }
+            },
+            ErasedInnerPtr::Many(list) => (0..)
+                .map_while(|idx| list.get_item(idx))
+                .find_map(|item| item.erase().nth_inner(n)),
+        }
+    }
+
+    /// Visit every item of type `T` as a pointer in lookup order, erasing each node once.
+    pub fn visit_ptr<T: 'static>(&self, f: &mut dyn FnMut(*mut T)) {
+        match *self {
+            ErasedInnerPtr::None => (),
+            ErasedInnerPtr::Any(ptr, type_id) => {
+                if type_id == TypeId::of::<T>() {
+                    f(ptr.cast())
+                }
+            }
+            ErasedInnerPtr::Joined(a, b) => {
+                a.erase().visit_ptr(f);
+                b.erase().visit_ptr(f);
+            }
+            ErasedInnerPtr::Struct(s) => {
+                for (idx, (ptr, type_id)) in (0..).map_while(|idx| Some((idx, s.get_field(idx)?))) {
+                    if let Some(nested) = s.get_nested(idx) {
+                        nested.erase().visit_ptr(f)
+                    } else if type_id == TypeId::of::<T>() {
+                        f(ptr.cast())
+                    }
+                }
+            }
+            ErasedInnerPtr::Many(list) => {
+                for item in (0..).map_while(|idx| list.get_item(idx)) {
+                    item.erase().visit_ptr(f)
+                }
+            }
+        }
+    }
 }
 
 /// A dyn compatible alternative to [`Any`] that can contain multiple items.
@@ -47,41 +646,104 @@ pub enum ErasedInnerPtr<'t> {
 /// ```
 ///
 /// The implementation can return `A`, or `(B, C)` but not both.
+///
+/// [`MetaAny::static_type_id`] and [`MetaAny::meta_type_id`] must return either `None`
+/// or the [`TypeId`] of `Self`.
 pub unsafe trait MetaAny {
     fn as_erased(&self) -> ErasedInner<'_>;
     fn as_erased_mut(&mut self) -> ErasedInnerMut<'_>;
-    fn as_erased_ptr(&self) -> ErasedInnerPtr<'_>;
-}
 
-use crate::impl_meta_any;
-impl_meta_any!(MetaAny);
+    /// Erase `self` with pointers derived from `this` instead of `self`.
+    ///
+    /// # Safety
+    ///
+    /// `this` must point to `self`, the result is valid for writes if `this` is.
+    unsafe fn as_erased_ptr(&self, this: *mut ()) -> ErasedInnerPtr<'_>;
 
-impl dyn MetaAny + '_ {
-    /// Obtain an item if it exists in the [`MetaAny`].
+    /// Erase a pointer without creating references, used by [`ErasedPtr`].
     ///
-    /// Always returns `Some` for `()`.
-    pub fn get_ptr<T: 'static>(&self) -> Option<*mut T> {
-        if (&() as &dyn Any).downcast_ref::<()>().is_some() {
-            return Some(NonNull::dangling().as_ptr());
-        }
-        match self.as_erased_ptr() {
-            ErasedInnerPtr::None => None,
-            ErasedInnerPtr::Any(any) => any.downcast_ref().map(|x| x as *const T as *mut T),
-            ErasedInnerPtr::Joined(a, b) => a.get_ptr().or_else(|| b.get_ptr()),
-            ErasedInnerPtr::Struct(s) => {
-                let mut idx = 0;
-                while let Some(field) = s.get_field(idx) {
-                    if let Some(result) = field.downcast_ref() {
-                        return Some(result as *const T as *mut T);
-                    }
-                    idx += 1;
-                }
-                None
-            }
-        }
+    /// By default this calls [`MetaAny::as_erased_ptr`] on a shared reference,
+    /// `&mut T` and boxed `dyn MetaAny` still create a shared reference to the pointee, since it may be unsized.
+    ///
+    /// # Safety
+    ///
+    /// `this` must be valid for reads for `'t`, the result is valid for writes if `this` is.
+    unsafe fn erase_ptr<'t>(this: *mut Self) -> ErasedInnerPtr<'t>
+    where
+        Self: Sized + 't,
+    {
+        unsafe { (*this).as_erased_ptr(this.cast()) }
+    }
+
+    /// Returns the [`TypeId`] of `Self`, or `None` if `Self` is not `'static`.
+    ///
+    /// By default returns `None`, which disables downcasting to `Self`.
+    fn static_type_id() -> Option<TypeId>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
+    /// Returns the [`TypeId`] of the concrete type, used by `downcast_ref` and `downcast_mut`.
+    ///
+    /// By default returns `None`, which disables downcasting to `Self`.
+    fn meta_type_id(&self) -> Option<TypeId> {
+        None
+    }
+
+    /// Visit every item and its type name in lookup order.
+    ///
+    /// By default walks [`MetaAny::as_erased`], see [`ErasedInner::visit`].
+    fn visit(&self, f: &mut dyn FnMut(&dyn Any, &'static str)) {
+        self.as_erased().visit(f)
+    }
+
+    /// Visit every mutable item and its type name in lookup order.
+    ///
+    /// Items joined by reference are not visited.
+    ///
+    /// By default walks [`MetaAny::as_erased_mut`], see [`ErasedInnerMut::visit_mut`].
+    fn visit_mut(&mut self, f: &mut dyn FnMut(&mut dyn Any, &'static str)) {
+        self.as_erased_mut().visit_mut(f)
+    }
+
+    /// Move all owned items into `sink` as boxed values with their type names,
+    /// used by [`MetaTuple::into_sink`] on boxed `dyn MetaAny`.
+    ///
+    /// # Panics
+    ///
+    /// The default implementation panics, implementors that own items should override this.
+    #[cfg(feature = "alloc")]
+    fn into_sink_boxed(
+        self: Box<Self>,
+        sink: &mut dyn FnMut(Box<dyn Any>, &'static str),
+    ) {
+        let _ = sink;
+        panic!(
+            "`{}` does not support moving items into a `MetaSink`.",
+            type_name::<Self>()
+        )
+    }
+
+    /// Returns the number of items.
+    fn len(&self) -> usize {
+        let mut len = 0;
+        self.visit(&mut |_, _| len += 1);
+        len
+    }
+
+    /// Returns `true` if there are no items.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 }
 
+use crate::impl_meta_any;
+impl_meta_any!(MetaAny);
+impl_meta_any!(MetaAny + Send);
+impl_meta_any!(MetaAny + Send + Sync);
+
 unsafe impl MetaAny for () {
     fn as_erased<'t>(&self) -> ErasedInner<'_> {
         ErasedInner::None
@@ -91,9 +753,23 @@ unsafe impl MetaAny for () {
         ErasedInnerMut::None
     }
 
-    fn as_erased_ptr(&self) -> ErasedInnerPtr<'_> {
+    unsafe fn as_erased_ptr(&self, _: *mut ()) -> ErasedInnerPtr<'_> {
         ErasedInnerPtr::None
     }
+
+    fn static_type_id() -> Option<TypeId> {
+        Some(TypeId::of::<Self>())
+    }
+
+    fn meta_type_id(&self) -> Option<TypeId> {
+        Self::static_type_id()
+    }
+
+    fn visit(&self, _: &mut dyn FnMut(&dyn Any, &'static str)) {}
+
+    fn visit_mut(&mut self, _: &mut dyn FnMut(&mut dyn Any, &'static str)) {}
+
+    crate::__impl_into_sink_boxed!();
 }
 
 unsafe impl<T: MetaAny + ?Sized> MetaAny for &T {
@@ -105,9 +781,18 @@ unsafe impl<T: MetaAny + ?Sized> MetaAny for &T {
         ErasedInnerMut::None
     }
 
-    fn as_erased_ptr(&self) -> ErasedInnerPtr<'_> {
+    unsafe fn as_erased_ptr(&self, _: *mut ()) -> ErasedInnerPtr<'_> {
         ErasedInnerPtr::None
     }
+
+    fn visit(&self, f: &mut dyn FnMut(&dyn Any, &'static str)) {
+        MetaAny::visit(*self, f)
+    }
+
+    fn visit_mut(&mut self, _: &mut dyn FnMut(&mut dyn Any, &'static str)) {}
+
+    #[cfg(feature = "alloc")]
+    fn into_sink_boxed(self: Box<Self>, _: &mut dyn FnMut(Box<dyn Any>, &'static str)) {}
 }
 
 unsafe impl<T: MetaAny + ?Sized> MetaAny for &mut T {
@@ -119,11 +804,75 @@ unsafe impl<T: MetaAny + ?Sized> MetaAny for &mut T {
         MetaAny::as_erased_mut(*self)
     }
 
-    fn as_erased_ptr(&self) -> ErasedInnerPtr<'_> {
-        MetaAny::as_erased_ptr(*self)
+    unsafe fn as_erased_ptr(&self, this: *mut ()) -> ErasedInnerPtr<'_> {
+        unsafe { Self::erase_ptr(this.cast()) }
     }
+
+    unsafe fn erase_ptr<'t>(this: *mut Self) -> ErasedInnerPtr<'t>
+    where
+        Self: 't,
+    {
+        // `T` may be unsized, erasing requires a shared reference to the pointee, but not to `this`.
+        let ptr = unsafe { &raw mut **this };
+        unsafe { MetaAny::as_erased_ptr(&*ptr, ptr.cast()) }
+    }
+
+    fn visit(&self, f: &mut dyn FnMut(&dyn Any, &'static str)) {
+        MetaAny::visit(*self, f)
+    }
+
+    fn visit_mut(&mut self, f: &mut dyn FnMut(&mut dyn Any, &'static str)) {
+        MetaAny::visit_mut(*self, f)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn into_sink_boxed(self: Box<Self>, _: &mut dyn FnMut(Box<dyn Any>, &'static str)) {}
+}
+
+/// Implements [`MetaAny`] for pointers to unsized types, erased by dynamic dispatch.
+#[cfg(feature = "alloc")]
+macro_rules! impl_meta_any_unsized {
+    ($($ty: ty),*) => {$(
+        unsafe impl MetaAny for $ty {
+            fn as_erased<'t>(&self) -> ErasedInner<'_> {
+                MetaAny::as_erased(&**self)
+            }
+
+            fn as_erased_mut(&mut self) -> ErasedInnerMut<'_> {
+                MetaAny::as_erased_mut(&mut **self)
+            }
+
+            unsafe fn as_erased_ptr(&self, this: *mut ()) -> ErasedInnerPtr<'_> {
+                unsafe { Self::erase_ptr(this.cast()) }
+            }
+
+            unsafe fn erase_ptr<'t>(this: *mut Self) -> ErasedInnerPtr<'t>
+            where
+                Self: 't,
+            {
+                // Dynamic dispatch requires a reference to the pointee, but not to `this`.
+                let ptr = unsafe { &raw mut **this };
+                unsafe { MetaAny::as_erased_ptr(&*ptr, ptr.cast()) }
+            }
+
+            fn visit(&self, f: &mut dyn FnMut(&dyn Any, &'static str)) {
+                MetaAny::visit(&**self, f)
+            }
+
+            fn visit_mut(&mut self, f: &mut dyn FnMut(&mut dyn Any, &'static str)) {
+                MetaAny::visit_mut(&mut **self, f)
+            }
+
+            fn into_sink_boxed(self: Box<Self>, sink: &mut dyn FnMut(Box<dyn Any>, &'static str)) {
+                MetaAny::into_sink_boxed(*self, sink)
+            }
+        }
+    )*};
 }
 
+#[cfg(feature = "alloc")]
+pub(crate) use impl_meta_any_unsized;
+
 unsafe impl<T: 'static> MetaAny for MetaItem<T> {
     fn as_erased<'t>(&self) -> ErasedInner<'_> {
         ErasedInner::Any(&self.0)
@@ -133,9 +882,34 @@ unsafe impl<T: 'static> MetaAny for MetaItem<T> {
         ErasedInnerMut::Any(&mut self.0)
     }
 
-    fn as_erased_ptr(&self) -> ErasedInnerPtr<'_> {
-        ErasedInnerPtr::Any(&self.0)
+    unsafe fn as_erased_ptr(&self, this: *mut ()) -> ErasedInnerPtr<'_> {
+        unsafe { Self::erase_ptr(this.cast()) }
+    }
+
+    unsafe fn erase_ptr<'t>(this: *mut Self) -> ErasedInnerPtr<'t>
+    where
+        Self: 't,
+    {
+        ErasedInnerPtr::Any(unsafe { &raw mut (*this).0 }.cast(), TypeId::of::<T>())
     }
+
+    fn static_type_id() -> Option<TypeId> {
+        Some(TypeId::of::<Self>())
+    }
+
+    fn meta_type_id(&self) -> Option<TypeId> {
+        Self::static_type_id()
+    }
+
+    fn visit(&self, f: &mut dyn FnMut(&dyn Any, &'static str)) {
+        f(&self.0, type_name::<T>())
+    }
+
+    fn visit_mut(&mut self, f: &mut dyn FnMut(&mut dyn Any, &'static str)) {
+        f(&mut self.0, type_name::<T>())
+    }
+
+    crate::__impl_into_sink_boxed!();
 }
 
 unsafe impl<T: 'static> MetaAny for Option<T> {
@@ -153,12 +927,41 @@ unsafe impl<T: 'static> MetaAny for Option<T> {
         }
     }
 
-    fn as_erased_ptr(&self) -> ErasedInnerPtr<'_> {
-        match self.as_ref() {
-            Some(value) => ErasedInnerPtr::Any(value),
+    unsafe fn as_erased_ptr(&self, this: *mut ()) -> ErasedInnerPtr<'_> {
+        unsafe { Self::erase_ptr(this.cast()) }
+    }
+
+    unsafe fn erase_ptr<'t>(this: *mut Self) -> ErasedInnerPtr<'t>
+    where
+        Self: 't,
+    {
+        match unsafe { project_some(this) } {
+            Some(value) => ErasedInnerPtr::Any(value.cast(), TypeId::of::<T>()),
             None => ErasedInnerPtr::None,
         }
     }
+
+    fn static_type_id() -> Option<TypeId> {
+        Some(TypeId::of::<Self>())
+    }
+
+    fn meta_type_id(&self) -> Option<TypeId> {
+        Self::static_type_id()
+    }
+
+    fn visit(&self, f: &mut dyn FnMut(&dyn Any, &'static str)) {
+        if let Some(value) = self {
+            f(value, type_name::<T>())
+        }
+    }
+
+    fn visit_mut(&mut self, f: &mut dyn FnMut(&mut dyn Any, &'static str)) {
+        if let Some(value) = self {
+            f(value, type_name::<T>())
+        }
+    }
+
+    crate::__impl_into_sink_boxed!();
 }
 
 unsafe impl<A: MetaTuple, B: MetaTuple> MetaAny for Join<A, B> {
@@ -170,12 +973,88 @@ unsafe impl<A: MetaTuple, B: MetaTuple> MetaAny for Join<A, B> {
         ErasedInnerMut::Joined(&mut self.0, &mut self.1)
     }
 
-    fn as_erased_ptr(&self) -> ErasedInnerPtr<'_> {
-        ErasedInnerPtr::Joined(&self.0, &self.1)
+    unsafe fn as_erased_ptr(&self, this: *mut ()) -> ErasedInnerPtr<'_> {
+        unsafe { Self::erase_ptr(this.cast()) }
+    }
+
+    unsafe fn erase_ptr<'t>(this: *mut Self) -> ErasedInnerPtr<'t>
+    where
+        Self: 't,
+    {
+        unsafe {
+            ErasedInnerPtr::Joined(
+                ErasedPtr::new(&raw mut (*this).0),
+                ErasedPtr::new(&raw mut (*this).1),
+            )
+        }
+    }
+
+    fn static_type_id() -> Option<TypeId> {
+        A::static_type_id()?;
+        B::static_type_id()?;
+        Some(erased_type_id::<Self>())
+    }
+
+    fn meta_type_id(&self) -> Option<TypeId> {
+        Self::static_type_id()
+    }
+
+    fn visit(&self, f: &mut dyn FnMut(&dyn Any, &'static str)) {
+        self.0.visit(f);
+        self.1.visit(f);
     }
+
+    fn visit_mut(&mut self, f: &mut dyn FnMut(&mut dyn Any, &'static str)) {
+        self.0.visit_mut(f);
+        self.1.visit_mut(f);
+    }
+
+    crate::__impl_into_sink_boxed!();
+}
+
+/// The fields of a [`MetaBundle`] as a read-only [`MetaAny`], used by `#[meta(item)]` with fields.
+#[doc(hidden)]
+#[repr(transparent)]
+pub struct BundleFields<B>(B);
+
+impl<B: MetaBundle> BundleFields<B> {
+    pub fn from_ref(bundle: &B) -> &Self {
+        // # Safety
+        //
+        // Safe since `#[repr(transparent)]`, same implementation as `ref_cast`.
+        unsafe { &*core::ptr::from_ref(bundle).cast::<Self>() }
+    }
+}
+
+unsafe impl<B: MetaBundle> MetaAny for BundleFields<B> {
+    fn as_erased(&self) -> ErasedInner<'_> {
+        ErasedInner::Struct(&self.0)
+    }
+
+    fn as_erased_mut(&mut self) -> ErasedInnerMut<'_> {
+        ErasedInnerMut::None
+    }
+
+    unsafe fn as_erased_ptr(&self, _: *mut ()) -> ErasedInnerPtr<'_> {
+        ErasedInnerPtr::None
+    }
+
+    unsafe fn erase_ptr<'t>(_: *mut Self) -> ErasedInnerPtr<'t>
+    where
+        Self: 't,
+    {
+        ErasedInnerPtr::None
+    }
+
+    fn visit_mut(&mut self, _: &mut dyn FnMut(&mut dyn Any, &'static str)) {}
 }
 
 /// [`MetaTuple`] implementation for [`dyn MetaAny`](crate::MetaAny).
+///
+/// # Panics
+///
+/// [`MetaTuple::get_all_mut`] panics without the `alloc` feature,
+/// since every item must be found before any is yielded.
 #[repr(transparent)]
 pub struct DynMetaTuple(pub dyn MetaAny);
 
@@ -204,8 +1083,25 @@ unsafe impl MetaAny for DynMetaTuple {
         self.0.as_erased_mut()
     }
 
-    fn as_erased_ptr(&self) -> ErasedInnerPtr<'_> {
-        self.0.as_erased_ptr()
+    unsafe fn as_erased_ptr(&self, this: *mut ()) -> ErasedInnerPtr<'_> {
+        unsafe { self.0.as_erased_ptr(this) }
+    }
+
+    fn visit(&self, f: &mut dyn FnMut(&dyn Any, &'static str)) {
+        self.0.visit(f)
+    }
+
+    fn visit_mut(&mut self, f: &mut dyn FnMut(&mut dyn Any, &'static str)) {
+        self.0.visit_mut(f)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn into_sink_boxed(self: Box<Self>, sink: &mut dyn FnMut(Box<dyn Any>, &'static str)) {
+        // # Safety
+        //
+        // Safe since `#[repr(transparent)]`, reverse of `DynMetaTuple::from_box`.
+        let inner: Box<dyn MetaAny> = unsafe { Box::from_raw(&raw mut (*Box::into_raw(self)).0) };
+        inner.into_sink_boxed(sink)
     }
 }
 
@@ -218,7 +1114,23 @@ unsafe impl MetaTuple for DynMetaTuple {
         self.0.get_mut()
     }
 
-    fn get_mut_ptr<T: 'static>(&self) -> Option<*mut T> {
-        self.0.get_ptr()
+    unsafe fn get_mut_ptr<T: 'static>(this: *mut Self) -> Option<*mut T> {
+        unsafe { <dyn MetaAny>::get_ptr(&raw mut (*this).0) }
+    }
+
+    fn get_all<T: 'static>(&self) -> impl Iterator<Item = &T> {
+        self.0.get_all()
+    }
+
+    #[cfg(feature = "alloc")]
+    fn get_all_mut<T: 'static>(&mut self) -> impl Iterator<Item = &mut T> {
+        self.0.get_all_mut()
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    fn get_all_mut<T: 'static>(&mut self) -> impl Iterator<Item = &mut T> {
+        core::iter::from_fn(|| -> Option<&mut T> {
+            panic!("`DynMetaTuple::get_all_mut` requires the `alloc` feature.")
+        })
     }
 }
@@ -0,0 +1,120 @@
+use crate::{MetaAny, MetaQuery};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::{any::type_name, fmt};
+use polonius_the_crab::{polonius, polonius_return};
+
+/// Error of a failed lookup or query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MetaError {
+    /// An item does not exist.
+    Missing { type_name: &'static str },
+    /// Two fields of a query access the same item and one of them is mutable.
+    Aliased { a: &'static str, b: &'static str },
+    /// An item exists but is not mutable, i.e. only reachable through `&MetaItem`.
+    NotMutable { type_name: &'static str },
+    /// An item exists but the query requires it to be absent, see [`Without`](crate::Without).
+    Unexpected { type_name: &'static str },
+    /// Multiple errors, reported by a query with multiple fields.
+    #[cfg(feature = "alloc")]
+    Many(Vec<MetaError>),
+}
+
+impl fmt::Display for MetaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetaError::Missing { type_name } => write!(f, "missing item {type_name}"),
+            MetaError::Aliased { a, b } => write!(f, "{a} and {b} access the same item"),
+            MetaError::NotMutable { type_name } => write!(f, "item {type_name} is not mutable"),
+            MetaError::Unexpected { type_name } => write!(f, "unexpected item {type_name}"),
+            #[cfg(feature = "alloc")]
+            MetaError::Many(errors) => {
+                for (idx, error) in errors.iter().enumerate() {
+                    if idx != 0 {
+                        f.write_str(", ")?;
+                    }
+                    fmt::Display::fmt(error, f)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl core::error::Error for MetaError {}
+
+impl MetaError {
+    /// Create an error for an item of type `T`, that is [`MetaError::NotMutable`] if `exists`.
+    pub(crate) fn not_found<T: 'static>(exists: bool) -> Self {
+        let type_name = type_name::<T>();
+        if exists {
+            MetaError::NotMutable { type_name }
+        } else {
+            MetaError::Missing { type_name }
+        }
+    }
+
+    /// Collect errors reported by a [`MetaQuery`].
+    ///
+    /// Without the `alloc` feature, only the first error is kept.
+    pub(crate) fn collect<Q: MetaQuery + ?Sized>(
+        f: impl FnOnce(&mut dyn FnMut(MetaError)),
+    ) -> Self {
+        #[cfg(feature = "alloc")]
+        {
+            let mut errors = Vec::new();
+            f(&mut |error| errors.push(error));
+            match errors.len() {
+                0 => MetaError::Missing {
+                    type_name: type_name::<Q>(),
+                },
+                1 => errors.pop().unwrap(),
+                _ => MetaError::Many(errors),
+            }
+        }
+        #[cfg(not(feature = "alloc"))]
+        {
+            let mut first = None;
+            f(&mut |error| {
+                first.get_or_insert(error);
+            });
+            first.unwrap_or(MetaError::Missing {
+                type_name: type_name::<Q>(),
+            })
+        }
+    }
+}
+
+/// Implementation of `try_get_mut` for subtraits of [`MetaAny`].
+#[doc(hidden)]
+pub fn try_get_mut_dyn<T: 'static>(mut input: &mut dyn MetaAny) -> Result<&mut T, MetaError> {
+    polonius!(|input| -> Result<&'polonius mut T, MetaError> {
+        if let Some(result) = input.get_mut::<T>() {
+            polonius_return!(Ok(result));
+        }
+    });
+    Err(MetaError::not_found::<T>(input.contains::<T>()))
+}
+
+/// Implementation of `try_query_ref` for subtraits of [`MetaAny`].
+#[doc(hidden)]
+pub fn try_query_ref_dyn<Q: MetaQuery>(input: &dyn MetaAny) -> Result<Q::Output<'_>, MetaError> {
+    Q::query_dyn_ref(input)
+        .ok_or_else(|| MetaError::collect::<Q>(|f| Q::report_dyn(input, false, f)))
+}
+
+/// Implementation of `try_query_mut` for subtraits of [`MetaAny`].
+#[doc(hidden)]
+pub fn try_query_mut_dyn<Q: MetaQuery>(
+    mut input: &mut dyn MetaAny,
+) -> Result<Q::Output<'_>, MetaError> {
+    if let Some(error) = Q::aliased() {
+        return Err(error);
+    }
+    polonius!(|input| -> Result<Q::Output<'polonius>, MetaError> {
+        if let Some(result) = Q::query_dyn_mut(input) {
+            polonius_return!(Ok(result));
+        }
+    });
+    Err(MetaError::collect::<Q>(|f| Q::report_dyn(input, true, f)))
+}
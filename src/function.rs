@@ -0,0 +1,61 @@
+use crate::{MetaQuery, MetaQuerySingle};
+
+/// A function whose arguments can be extracted from a [`MetaTuple`](crate::MetaTuple) by type,
+/// see [`MetaTuple::call`](crate::MetaTuple::call).
+///
+/// Implemented for functions and closures of up to 12 arguments,
+/// where each argument is a query like `&A`, `&mut B` or `Option<&C>`.
+///
+/// ```
+/// fn attack(attacker: &Attacker, defender: &Defender) -> DamageDealt {
+///     ...
+/// }
+///
+/// let damage = tuple.call(attack).unwrap();
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be called with arguments from a `MetaTuple`",
+    label = "arguments must be queries like `&A`, `&mut B` or `Option<&C>`"
+)]
+pub trait MetaFn<Args: MetaQuery> {
+    /// The return type of the function.
+    type Output;
+
+    /// Call the function with queried arguments.
+    fn call_meta(&mut self, args: Args::Output<'_>) -> Self::Output;
+}
+
+/// Forces the compiler to use the higher ranked `FnMut` implementation.
+fn call_inner<Args, R>(mut f: impl FnMut(Args) -> R, args: Args) -> R {
+    f(args)
+}
+
+macro_rules! impl_meta_fn {
+    ($($T: ident)*) => {
+        #[allow(non_snake_case, clippy::unused_unit)]
+        impl<Func, R, $($T: MetaQuerySingle + 'static),*> MetaFn<($($T,)*)> for Func
+        where
+            Func: FnMut($($T),*) -> R + for<'t> FnMut($($T::Output<'t>),*) -> R,
+        {
+            type Output = R;
+
+            fn call_meta(&mut self, args: <($($T,)*) as MetaQuery>::Output<'_>) -> R {
+                call_inner(|($($T,)*)| self($($T),*), args)
+            }
+        }
+    };
+}
+
+impl_meta_fn!();
+impl_meta_fn!(T0);
+impl_meta_fn!(T0 T1);
+impl_meta_fn!(T0 T1 T2);
+impl_meta_fn!(T0 T1 T2 T3);
+impl_meta_fn!(T0 T1 T2 T3 T4);
+impl_meta_fn!(T0 T1 T2 T3 T4 T5);
+impl_meta_fn!(T0 T1 T2 T3 T4 T5 T6);
+impl_meta_fn!(T0 T1 T2 T3 T4 T5 T6 T7);
+impl_meta_fn!(T0 T1 T2 T3 T4 T5 T6 T7 T8);
+impl_meta_fn!(T0 T1 T2 T3 T4 T5 T6 T7 T8 T9);
+impl_meta_fn!(T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10);
+impl_meta_fn!(T0 T1 T2 T3 T4 T5 T6 T7 T8 T9 T10 T11);
@@ -1,4 +1,8 @@
 use crate::{MetaTuple, meta_tuple};
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use core::any::Any;
 
 /// A trait that converts a more user friendly concrete type into a [`MetaTuple`].
 ///
@@ -16,6 +20,39 @@ where
     }
 }
 
+/// A collection that owned items of a [`MetaTuple`] can be moved into.
+///
+/// See [`MetaTuple::into_sink`].
+pub trait MetaSink {
+    /// Add an item to the collection.
+    fn push<T: 'static>(&mut self, item: T);
+
+    /// Add a boxed item and its type name to the collection.
+    ///
+    /// By default this adds the [`Box`] itself as an item,
+    /// dynamic collections should unwrap the box instead.
+    #[cfg(feature = "alloc")]
+    fn push_boxed(&mut self, item: Box<dyn Any>, _type_name: &'static str) {
+        self.push(item)
+    }
+}
+
+/// A [`MetaSink`] that boxes every item, used by [`MetaAny::into_sink_boxed`](crate::MetaAny::into_sink_boxed).
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub struct BoxedSink<'t>(pub &'t mut dyn FnMut(Box<dyn Any>, &'static str));
+
+#[cfg(feature = "alloc")]
+impl MetaSink for BoxedSink<'_> {
+    fn push<T: 'static>(&mut self, item: T) {
+        (self.0)(Box::new(item), core::any::type_name::<T>())
+    }
+
+    fn push_boxed(&mut self, item: Box<dyn Any>, type_name: &'static str) {
+        (self.0)(item, type_name)
+    }
+}
+
 macro_rules! impl_tuple {
     ($($T: ident),*) => {
         #[allow(non_snake_case)]
@@ -1,23 +1,72 @@
 #![no_std]
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+mod boxed;
 mod dynamic;
+mod error;
+mod function;
 mod into;
 mod item;
+mod list;
+#[cfg(feature = "alloc")]
+mod map;
 mod r#macro;
+pub mod pipeline;
 mod query;
+#[cfg(feature = "serde")]
+mod registry;
+#[cfg(feature = "alloc")]
+mod remainder;
+mod select;
+#[cfg(feature = "alloc")]
+mod vec;
 
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub use alloc::{boxed::Box, vec::Vec};
+#[cfg(feature = "alloc")]
+pub use boxed::DowncastBox;
 #[doc(hidden)]
 pub use core::any::Any;
+#[doc(hidden)]
+pub use core::cell::RefCell;
 pub use dynamic::*;
-pub use into::IntoMetaTuple;
+pub use error::MetaError;
+#[doc(hidden)]
+pub use error::{try_get_mut_dyn, try_query_mut_dyn, try_query_ref_dyn};
+pub use function::MetaFn;
+pub use into::{IntoMetaTuple, MetaSink};
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub use into::BoxedSink;
 pub use item::MetaItem;
-pub use query::MetaQuery;
+#[cfg(feature = "alloc")]
+pub use map::{MetaEntry, MetaMap};
+pub use pipeline::MetaStage;
+pub use query::{Access, All, AnyOf, Cloned, Copied, Has, MetaQuery, Or, OrDefault, Without};
 #[doc(hidden)]
-pub use query::MetaQuerySingle;
+pub use query::{AllQuery, AnyOfQuery, MetaQuerySingle, conflict};
+#[cfg(feature = "serde")]
+pub use registry::{MetaRegistry, SerializeMeta, Unregistered};
+#[cfg(feature = "alloc")]
+pub use remainder::Remainder;
+pub use select::{Field, GetStatic, Here, Left, Right, Select, SelectMut, SplitQuery, Take};
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub use std::sync::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+#[cfg(feature = "alloc")]
+pub use vec::{FromMetaVec, MetaVec};
 
 #[cfg(feature = "derive")]
 pub use meta_tuple_derive::{MetaItem, MetaQuery, MetaTuple};
 #[doc(hidden)]
 pub use polonius_the_crab::{polonius_loop, polonius_return};
+use core::any::TypeId;
+use polonius_the_crab::polonius;
 
 /// A statically typed opaque tuple that can contain any type.
 ///
@@ -51,6 +100,11 @@ pub use polonius_the_crab::{polonius_loop, polonius_return};
 /// For functions like `get`, we look for the first correct item, duplicated items will not be used.
 /// `&impl MetaTuple` and `&mut impl MetaTuple` both implement MetaTuple.
 ///
+/// Zero sized types, including `()`, are regular items, `get::<()>()` returns `Some`
+/// only if a `()` item exists. The empty [`MetaTuple`] `()` contains no items.
+///
+/// Lists like `[M; N]`, `&[&dyn MetaAny]` and `Vec<Box<dyn MetaAny>>` are searched front to back.
+///
 /// ## Warning
 /// Due to our semantics, for a tuple like `(&A, A)`,
 /// `get` returns the first value while `get_mut` returns the second value,
@@ -79,8 +133,148 @@ pub unsafe trait MetaTuple: MetaAny {
     fn get<T: 'static>(&self) -> Option<&T>;
     /// Obtain a mutable item, if exists.
     fn get_mut<T: 'static>(&mut self) -> Option<&mut T>;
-    /// Obtain a mutable item as pointer, if exists.
-    fn get_mut_ptr<T: 'static>(&self) -> Option<*mut T>;
+    /// Obtain a mutable item as a pointer derived from `this`, if exists.
+    ///
+    /// No references to `*this` are created, so pointers to different items do not invalidate each other.
+    ///
+    /// # Safety
+    ///
+    /// `this` must be valid for reads, the result is valid for writes if `this` is.
+    unsafe fn get_mut_ptr<T: 'static>(this: *mut Self) -> Option<*mut T>;
+
+    /// Obtain all items of type `T` in lookup order.
+    fn get_all<T: 'static>(&self) -> impl Iterator<Item = &T>;
+
+    /// Obtain all mutable items of type `T` in lookup order.
+    fn get_all_mut<T: 'static>(&mut self) -> impl Iterator<Item = &mut T>;
+
+    /// Returns the number of items of type `T`.
+    fn count<T: 'static>(&self) -> usize {
+        self.get_all::<T>().count()
+    }
+
+    /// Obtain the `n`th item of type `T`, if exists.
+    fn get_nth<T: 'static>(&self, n: usize) -> Option<&T> {
+        self.get_all().nth(n)
+    }
+
+    /// Obtain the `n`th mutable item of type `T`, if exists.
+    fn get_nth_mut<T: 'static>(&mut self, n: usize) -> Option<&mut T> {
+        self.get_all_mut().nth(n)
+    }
+
+    /// Returns `true` if an item of type `T` exists.
+    fn contains<T: 'static>(&self) -> bool {
+        self.get::<T>().is_some()
+    }
+
+    /// Borrow an item stored as `RefCell<T>`, if exists.
+    ///
+    /// # Panics
+    ///
+    /// If the item is currently mutably borrowed.
+    fn borrow<T: 'static>(&self) -> Option<core::cell::Ref<'_, T>> {
+        self.get::<RefCell<T>>().map(RefCell::borrow)
+    }
+
+    /// Mutably borrow an item stored as `RefCell<T>`, if exists.
+    ///
+    /// # Panics
+    ///
+    /// If the item is currently borrowed.
+    fn borrow_mut<T: 'static>(&self) -> Option<core::cell::RefMut<'_, T>> {
+        self.get::<RefCell<T>>().map(RefCell::borrow_mut)
+    }
+
+    /// Lock an item stored as `Mutex<T>`, if exists.
+    ///
+    /// # Panics
+    ///
+    /// If the lock is poisoned.
+    #[cfg(feature = "std")]
+    fn lock<T: 'static>(&self) -> Option<MutexGuard<'_, T>> {
+        self.get::<Mutex<T>>().map(|x| x.lock().unwrap())
+    }
+
+    /// Lock an item stored as `RwLock<T>` with shared read access, if exists.
+    ///
+    /// # Panics
+    ///
+    /// If the lock is poisoned.
+    #[cfg(feature = "std")]
+    fn read<T: 'static>(&self) -> Option<RwLockReadGuard<'_, T>> {
+        self.get::<RwLock<T>>().map(|x| x.read().unwrap())
+    }
+
+    /// Lock an item stored as `RwLock<T>` with exclusive write access, if exists.
+    ///
+    /// # Panics
+    ///
+    /// If the lock is poisoned.
+    #[cfg(feature = "std")]
+    fn write<T: 'static>(&self) -> Option<RwLockWriteGuard<'_, T>> {
+        self.get::<RwLock<T>>().map(|x| x.write().unwrap())
+    }
+
+    /// Obtain the [`TypeId`]s of all items in lookup order.
+    #[cfg(feature = "alloc")]
+    fn type_ids(&self) -> impl Iterator<Item = TypeId> {
+        let mut type_ids = alloc::vec::Vec::new();
+        self.visit(&mut |item, _| type_ids.push(Any::type_id(item)));
+        type_ids.into_iter()
+    }
+
+    /// Move an item that is statically known to exist out of the [`MetaTuple`].
+    ///
+    /// The index `I` is inferred, see [`Take`].
+    fn take<T: 'static, I>(self) -> T
+    where
+        Self: Take<T, I>,
+    {
+        self.take_item().0
+    }
+
+    /// Move an item that is statically known to exist out of the [`MetaTuple`],
+    /// returns the item and the remaining [`MetaTuple`].
+    ///
+    /// The index `I` is inferred, see [`Take`].
+    fn split<T: 'static, I>(self) -> (T, <Self as Take<T, I>>::Rest)
+    where
+        Self: Take<T, I>,
+    {
+        self.take_item()
+    }
+
+    /// Move multiple items out of the [`MetaTuple`], returns the items and the remaining [`MetaTuple`].
+    ///
+    /// ```
+    /// let tuple = meta_tuple!(1i32, 2.0f32, "hello");
+    /// let ((a, b), rest) = tuple.split_query::<(i32, &str), _>();
+    /// ```
+    fn split_query<Q, I>(self) -> (Q, <Self as SplitQuery<Q, I>>::Rest)
+    where
+        Self: SplitQuery<Q, I>,
+    {
+        self.split_items()
+    }
+
+    /// Move all owned items into a [`MetaSink`] in lookup order.
+    ///
+    /// Items joined by reference are not moved.
+    ///
+    /// # Panics
+    ///
+    /// The default implementation panics, implementors that own items should override this.
+    fn into_sink(self, sink: &mut impl MetaSink)
+    where
+        Self: Sized,
+    {
+        let _ = sink;
+        panic!(
+            "`{}` does not support moving items into a `MetaSink`.",
+            core::any::type_name::<Self>()
+        )
+    }
 
     /// Join with another concrete value.
     fn join<T: 'static>(self, other: T) -> Join<Self, MetaItem<T>>
@@ -132,6 +326,15 @@ pub unsafe trait MetaTuple: MetaAny {
         Join(self, DynMetaTuple::from_mut(other))
     }
 
+    /// Box as a `dyn MetaAny`, which also implements [`MetaTuple`].
+    #[cfg(feature = "alloc")]
+    fn into_dyn<'t>(self) -> alloc::boxed::Box<dyn MetaAny + 't>
+    where
+        Self: Sized + 't,
+    {
+        alloc::boxed::Box::new(self)
+    }
+
 
     /// Try obtain multiple values from the [`MetaTuple`].
     fn query_ref<T: MetaQuery>(&self) -> Option<T::Output<'_>> {
@@ -139,9 +342,95 @@ pub unsafe trait MetaTuple: MetaAny {
     }
 
     /// Try obtain multiple values from the [`MetaTuple`].
+    ///
+    /// # Panics
+    ///
+    /// If fields alias, like `(&mut A, &mut A)`.
+    ///
+    /// Plain tuple queries are only checked at runtime, since types cannot be compared
+    /// in const contexts, use a derived [`MetaQuery`] to reject aliasing at compile time.
     fn query_mut<T: MetaQuery>(&mut self) -> Option<T::Output<'_>> {
         T::query_mut(self)
     }
+
+    /// Try obtain multiple values from the [`MetaTuple`], and a [`Remainder`] of the other items,
+    /// requires the `alloc` feature.
+    ///
+    /// The [`Remainder`] hides items accessed mutably by the query and
+    /// only allows reading items the query reads, so both can be used at the same time.
+    ///
+    /// # Panics
+    ///
+    /// If fields alias, like `(&mut A, &A)`.
+    #[cfg(feature = "alloc")]
+    fn split_query_mut<Q: MetaQuery>(&mut self) -> Option<(Q::Output<'_>, Remainder<'_>)> {
+        Remainder::split::<Q, Self>(self)
+    }
+
+    /// Obtain an item, returns [`MetaError::Missing`] if not exists.
+    fn try_get<T: 'static>(&self) -> Result<&T, MetaError> {
+        self.get().ok_or(MetaError::not_found::<T>(false))
+    }
+
+    /// Obtain a mutable item, returns [`MetaError::NotMutable`] if the item is not mutable.
+    fn try_get_mut<T: 'static>(&mut self) -> Result<&mut T, MetaError> {
+        let mut this = self;
+        polonius!(|this| -> Result<&'polonius mut T, MetaError> {
+            if let Some(result) = this.get_mut::<T>() {
+                polonius_return!(Ok(result));
+            }
+        });
+        Err(MetaError::not_found::<T>(this.contains::<T>()))
+    }
+
+    /// Try obtain multiple values from the [`MetaTuple`], reports every missing field on failure.
+    fn try_query_ref<Q: MetaQuery>(&self) -> Result<Q::Output<'_>, MetaError> {
+        Q::query_ref(self).ok_or_else(|| MetaError::collect::<Q>(|f| Q::report(self, false, f)))
+    }
+
+    /// Try obtain multiple values from the [`MetaTuple`], reports every missing field on failure.
+    ///
+    /// Unlike `query_mut`, returns [`MetaError::Aliased`] instead of panicking if fields alias.
+    fn try_query_mut<Q: MetaQuery>(&mut self) -> Result<Q::Output<'_>, MetaError> {
+        if let Some(error) = Q::aliased() {
+            return Err(error);
+        }
+        let mut this = self;
+        polonius!(|this| -> Result<Q::Output<'polonius>, MetaError> {
+            if let Some(result) = Q::query_mut(this) {
+                polonius_return!(Ok(result));
+            }
+        });
+        Err(MetaError::collect::<Q>(|f| Q::report(this, true, f)))
+    }
+
+    /// Call a function with arguments obtained from the [`MetaTuple`], see [`MetaFn`].
+    ///
+    /// Returns `None` if an argument cannot be obtained.
+    ///
+    /// # Panics
+    ///
+    /// If arguments alias, like `(&mut A, &A)`.
+    fn call<Args: MetaQuery, F: MetaFn<Args>>(&mut self, mut f: F) -> Option<F::Output> {
+        Some(f.call_meta(self.query_mut::<Args>()?))
+    }
+
+    /// Call a function with arguments obtained from the [`MetaTuple`], see [`MetaFn`],
+    /// then join with the return value.
+    ///
+    /// The joined item does not exist if an argument cannot be obtained.
+    ///
+    /// # Panics
+    ///
+    /// If arguments alias, like `(&mut A, &A)`.
+    fn call_join<Args: MetaQuery, F: MetaFn<Args>>(mut self, f: F) -> Join<Self, Option<F::Output>>
+    where
+        Self: Sized,
+        F::Output: 'static,
+    {
+        let output = self.call(f);
+        Join(self, output)
+    }
 }
 
 unsafe impl<T: MetaTuple + ?Sized> MetaTuple for &T {
@@ -153,9 +442,19 @@ unsafe impl<T: MetaTuple + ?Sized> MetaTuple for &T {
         None
     }
 
-    fn get_mut_ptr<U: 'static>(&self) -> Option<*mut U> {
+    unsafe fn get_mut_ptr<U: 'static>(_: *mut Self) -> Option<*mut U> {
         None
     }
+
+    fn get_all<U: 'static>(&self) -> impl Iterator<Item = &U> {
+        MetaTuple::get_all(*self)
+    }
+
+    fn get_all_mut<U: 'static>(&mut self) -> impl Iterator<Item = &mut U> {
+        core::iter::empty()
+    }
+
+    fn into_sink(self, _: &mut impl MetaSink) {}
 }
 
 unsafe impl<T: MetaTuple + ?Sized> MetaTuple for &mut T {
@@ -167,9 +466,19 @@ unsafe impl<T: MetaTuple + ?Sized> MetaTuple for &mut T {
         MetaTuple::get_mut(*self)
     }
 
-    fn get_mut_ptr<U: 'static>(&self) -> Option<*mut U> {
-        MetaTuple::get_mut_ptr(*self)
+    unsafe fn get_mut_ptr<U: 'static>(this: *mut Self) -> Option<*mut U> {
+        unsafe { T::get_mut_ptr(&raw mut **this) }
     }
+
+    fn get_all<U: 'static>(&self) -> impl Iterator<Item = &U> {
+        MetaTuple::get_all(*self)
+    }
+
+    fn get_all_mut<U: 'static>(&mut self) -> impl Iterator<Item = &mut U> {
+        MetaTuple::get_all_mut(*self)
+    }
+
+    fn into_sink(self, _: &mut impl MetaSink) {}
 }
 
 unsafe impl MetaTuple for () {
@@ -181,9 +490,19 @@ unsafe impl MetaTuple for () {
         None
     }
 
-    fn get_mut_ptr<T: 'static>(&self) -> Option<*mut T> {
+    unsafe fn get_mut_ptr<T: 'static>(_: *mut Self) -> Option<*mut T> {
         None
     }
+
+    fn get_all<T: 'static>(&self) -> impl Iterator<Item = &T> {
+        core::iter::empty()
+    }
+
+    fn get_all_mut<T: 'static>(&mut self) -> impl Iterator<Item = &mut T> {
+        core::iter::empty()
+    }
+
+    fn into_sink(self, _: &mut impl MetaSink) {}
 }
 
 unsafe impl<T: 'static> MetaTuple for MetaItem<T> {
@@ -195,14 +514,25 @@ unsafe impl<T: 'static> MetaTuple for MetaItem<T> {
         (&mut self.0 as &mut dyn Any).downcast_mut()
     }
 
-    fn get_mut_ptr<U: 'static>(&self) -> Option<*mut U> {
-        (&self.0 as &dyn Any)
-            .downcast_ref()
-            .map(|x| x as *const U as *mut U)
+    unsafe fn get_mut_ptr<U: 'static>(this: *mut Self) -> Option<*mut U> {
+        (TypeId::of::<T>() == TypeId::of::<U>()).then(|| unsafe { &raw mut (*this).0 }.cast())
+    }
+
+    fn get_all<U: 'static>(&self) -> impl Iterator<Item = &U> {
+        MetaTuple::get(self).into_iter()
+    }
+
+    fn get_all_mut<U: 'static>(&mut self) -> impl Iterator<Item = &mut U> {
+        MetaTuple::get_mut(self).into_iter()
+    }
+
+    fn into_sink(self, sink: &mut impl MetaSink) {
+        sink.push(self.0)
     }
 }
 
 unsafe impl<T: 'static> MetaTuple for Option<T> {
+    #[allow(clippy::collapsible_if)]
     fn get<U: 'static>(&self) -> Option<&U> {
         if let Some(v) = self.as_ref() {
             if let Some(result) = (v as &dyn Any).downcast_ref() {
@@ -212,6 +542,7 @@ unsafe impl<T: 'static> MetaTuple for Option<T> {
         None
     }
 
+    #[allow(clippy::collapsible_if)]
     fn get_mut<U: 'static>(&mut self) -> Option<&mut U> {
         if let Some(v) = self.as_mut() {
             if let Some(result) = (v as &mut dyn Any).downcast_mut() {
@@ -221,13 +552,25 @@ unsafe impl<T: 'static> MetaTuple for Option<T> {
         None
     }
 
-    fn get_mut_ptr<U: 'static>(&self) -> Option<*mut U> {
-        if let Some(v) = self.as_ref() {
-            if let Some(result) = (v as &dyn Any).downcast_ref() {
-                return Some(result as *const U as *mut U);
-            }
+    unsafe fn get_mut_ptr<U: 'static>(this: *mut Self) -> Option<*mut U> {
+        if TypeId::of::<T>() != TypeId::of::<U>() {
+            return None;
+        }
+        Some(unsafe { dynamic::project_some(this) }?.cast())
+    }
+
+    fn get_all<U: 'static>(&self) -> impl Iterator<Item = &U> {
+        MetaTuple::get(self).into_iter()
+    }
+
+    fn get_all_mut<U: 'static>(&mut self) -> impl Iterator<Item = &mut U> {
+        MetaTuple::get_mut(self).into_iter()
+    }
+
+    fn into_sink(self, sink: &mut impl MetaSink) {
+        if let Some(value) = self {
+            sink.push(value)
         }
-        None
     }
 }
 
@@ -244,7 +587,20 @@ unsafe impl<A: MetaTuple, B: MetaTuple> MetaTuple for Join<A, B> {
         self.0.get_mut().or_else(|| self.1.get_mut())
     }
 
-    fn get_mut_ptr<T: 'static>(&self) -> Option<*mut T> {
-        self.0.get_mut_ptr().or_else(|| self.1.get_mut_ptr())
+    unsafe fn get_mut_ptr<T: 'static>(this: *mut Self) -> Option<*mut T> {
+        unsafe { A::get_mut_ptr(&raw mut (*this).0).or_else(|| B::get_mut_ptr(&raw mut (*this).1)) }
+    }
+
+    fn get_all<T: 'static>(&self) -> impl Iterator<Item = &T> {
+        self.0.get_all().chain(self.1.get_all())
+    }
+
+    fn get_all_mut<T: 'static>(&mut self) -> impl Iterator<Item = &mut T> {
+        self.0.get_all_mut().chain(self.1.get_all_mut())
+    }
+
+    fn into_sink(self, sink: &mut impl MetaSink) {
+        self.0.into_sink(sink);
+        self.1.into_sink(sink);
     }
 }
@@ -0,0 +1,168 @@
+use crate::{
+    ErasedInner, ErasedInnerMut, ErasedInnerPtr, ErasedListPtr, ErasedPtr, MetaAny, MetaList,
+    MetaSink, MetaTuple, dynamic::erased_type_id,
+};
+use core::{
+    any::{Any, TypeId},
+    iter,
+};
+
+impl<M: MetaTuple, const N: usize> MetaList for [M; N] {
+    fn get_item(&self, idx: usize) -> Option<&dyn MetaAny> {
+        self.as_slice().get(idx).map(|item| item as &dyn MetaAny)
+    }
+
+    fn get_item_mut(&mut self, idx: usize) -> Option<&mut dyn MetaAny> {
+        self.as_mut_slice()
+            .get_mut(idx)
+            .map(|item| item as &mut dyn MetaAny)
+    }
+
+    unsafe fn get_item_ptr<'t>(this: *mut Self, idx: usize) -> Option<ErasedPtr<'t>>
+    where
+        Self: 't,
+    {
+        (idx < N).then(|| unsafe { ErasedPtr::new(this.cast::<M>().add(idx)) })
+    }
+}
+
+unsafe impl<M: MetaTuple, const N: usize> MetaAny for [M; N] {
+    fn as_erased(&self) -> ErasedInner<'_> {
+        ErasedInner::Many(self)
+    }
+
+    fn as_erased_mut(&mut self) -> ErasedInnerMut<'_> {
+        ErasedInnerMut::Many(self)
+    }
+
+    unsafe fn as_erased_ptr(&self, this: *mut ()) -> ErasedInnerPtr<'_> {
+        unsafe { Self::erase_ptr(this.cast()) }
+    }
+
+    unsafe fn erase_ptr<'t>(this: *mut Self) -> ErasedInnerPtr<'t>
+    where
+        Self: 't,
+    {
+        ErasedInnerPtr::Many(unsafe { ErasedListPtr::new(this) })
+    }
+
+    fn static_type_id() -> Option<TypeId> {
+        M::static_type_id()?;
+        Some(erased_type_id::<Self>())
+    }
+
+    fn meta_type_id(&self) -> Option<TypeId> {
+        Self::static_type_id()
+    }
+
+    fn visit(&self, f: &mut dyn FnMut(&dyn Any, &'static str)) {
+        for item in self {
+            item.visit(f)
+        }
+    }
+
+    fn visit_mut(&mut self, f: &mut dyn FnMut(&mut dyn Any, &'static str)) {
+        for item in self {
+            item.visit_mut(f)
+        }
+    }
+
+    crate::__impl_into_sink_boxed!();
+}
+
+unsafe impl<M: MetaTuple, const N: usize> MetaTuple for [M; N] {
+    fn get<T: 'static>(&self) -> Option<&T> {
+        self.iter().find_map(|item| item.get())
+    }
+
+    fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.iter_mut().find_map(|item| item.get_mut())
+    }
+
+    unsafe fn get_mut_ptr<T: 'static>(this: *mut Self) -> Option<*mut T> {
+        (0..N).find_map(|idx| unsafe { M::get_mut_ptr(this.cast::<M>().add(idx)) })
+    }
+
+    fn get_all<T: 'static>(&self) -> impl Iterator<Item = &T> {
+        self.iter().flat_map(|item| item.get_all())
+    }
+
+    fn get_all_mut<T: 'static>(&mut self) -> impl Iterator<Item = &mut T> {
+        self.iter_mut().flat_map(|item| item.get_all_mut())
+    }
+
+    fn into_sink(self, sink: &mut impl MetaSink) {
+        for item in self {
+            item.into_sink(sink)
+        }
+    }
+}
+
+/// Items are not mutable since they are borrowed.
+impl MetaList for &[&dyn MetaAny] {
+    fn get_item(&self, idx: usize) -> Option<&dyn MetaAny> {
+        (**self).get(idx).copied()
+    }
+
+    fn get_item_mut(&mut self, _: usize) -> Option<&mut dyn MetaAny> {
+        None
+    }
+
+    unsafe fn get_item_ptr<'t>(_: *mut Self, _: usize) -> Option<ErasedPtr<'t>>
+    where
+        Self: 't,
+    {
+        None
+    }
+}
+
+unsafe impl MetaAny for &[&dyn MetaAny] {
+    fn as_erased(&self) -> ErasedInner<'_> {
+        ErasedInner::Many(self)
+    }
+
+    fn as_erased_mut(&mut self) -> ErasedInnerMut<'_> {
+        ErasedInnerMut::None
+    }
+
+    unsafe fn as_erased_ptr(&self, _: *mut ()) -> ErasedInnerPtr<'_> {
+        ErasedInnerPtr::None
+    }
+
+    fn visit(&self, f: &mut dyn FnMut(&dyn Any, &'static str)) {
+        for item in *self {
+            item.visit(f)
+        }
+    }
+
+    fn visit_mut(&mut self, _: &mut dyn FnMut(&mut dyn Any, &'static str)) {}
+
+    crate::__impl_into_sink_boxed!();
+}
+
+/// Items are not mutable since they are borrowed.
+///
+/// Slice methods shadow `get` and `get_mut`, call them as `MetaTuple::get(&slice)`.
+unsafe impl MetaTuple for &[&dyn MetaAny] {
+    fn get<T: 'static>(&self) -> Option<&T> {
+        self.iter().find_map(|item| item.get())
+    }
+
+    fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        None
+    }
+
+    unsafe fn get_mut_ptr<T: 'static>(_: *mut Self) -> Option<*mut T> {
+        None
+    }
+
+    fn get_all<T: 'static>(&self) -> impl Iterator<Item = &T> {
+        self.iter().flat_map(|item| item.get_all())
+    }
+
+    fn get_all_mut<T: 'static>(&mut self) -> impl Iterator<Item = &mut T> {
+        iter::empty()
+    }
+
+    fn into_sink(self, _: &mut impl MetaSink) {}
+}
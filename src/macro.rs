@@ -111,27 +111,34 @@ macro_rules! meta_tuple_type {
 ///
 /// impl_meta_any!(Metadata);
 /// ```
+///
+/// Paths and auto trait bounds are accepted, generic parameters are declared in brackets.
+///
+/// ```
+/// impl_meta_any!(crate::ctx::Metadata + Send);
+/// impl_meta_any!([P: 'static] dyn Context<P> + Send + Sync);
+/// ```
 #[macro_export]
 macro_rules! impl_meta_any {
-    ($trait: ident) => {
+    ([$($generics: tt)*] dyn $($bounds: tt)+) => {
         const _: () = {
             use $crate::MetaAny;
-            impl dyn $trait + '_ {
+            impl<$($generics)*> dyn $($bounds)+ + '_ {
                 /// Obtain an item if it exists in the [`MetaAny`].
-                ///
-                /// Always returns `Some` for `()`.
                 pub fn get<T: 'static>(&self) -> Option<&T> {
-                    if let Some(value) = (&() as &dyn $crate::Any).downcast_ref() {
-                        return Some(value);
-                    }
                     match self.as_erased() {
                         $crate::ErasedInner::None => None,
                         $crate::ErasedInner::Any(any) => any.downcast_ref(),
                         $crate::ErasedInner::Joined(a, b) => a.get().or_else(|| b.get()),
+                        erased @ $crate::ErasedInner::Struct(s) if s.has_nested() => erased.get_nth(0),
                         $crate::ErasedInner::Struct(s) => {
+                            let idx = s.find_field(::core::any::TypeId::of::<T>(), 0).ok()?;
+                            s.get_field(idx)?.downcast_ref()
+                        }
+                        $crate::ErasedInner::Many(list) => {
                             let mut idx = 0;
-                            while let Some(field) = s.get_field(idx) {
-                                if let Some(result) = field.downcast_ref() {
+                            while let Some(item) = list.get_item(idx) {
+                                if let Some(result) = item.get() {
                                     return Some(result);
                                 }
                                 idx += 1;
@@ -141,30 +148,26 @@ macro_rules! impl_meta_any {
                     }
                 }
 
-                /// Obtain an item if it exists in the [`MetaAny`].
-                ///
-                /// Always returns `Some` for `()`.
+                /// Obtain a mutable item if it exists in the [`MetaAny`].
                 pub fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
-                    if (&mut () as &mut dyn $crate::Any)
-                        .downcast_mut::<T>()
-                        .is_some()
-                    {
-                        // Safety:
-                        //
-                        // Safe since `()` is a ZST.
-                        return Some(unsafe { ::core::ptr::NonNull::dangling().as_mut() });
-                    }
                     match self.as_erased_mut() {
                         $crate::ErasedInnerMut::None => None,
                         $crate::ErasedInnerMut::Any(any) => any.downcast_mut(),
                         $crate::ErasedInnerMut::Joined(a, b) => a.get_mut().or_else(|| b.get_mut()),
-                        $crate::ErasedInnerMut::Struct(mut s) => {
+                        $crate::ErasedInnerMut::Struct(s) if s.has_nested() => {
+                            $crate::ErasedInnerMut::Struct(s).get_nth(0)
+                        }
+                        $crate::ErasedInnerMut::Struct(s) => {
+                            let idx = s.find_field(::core::any::TypeId::of::<T>(), 0).ok()?;
+                            s.get_field_mut(idx)?.downcast_mut()
+                        }
+                        $crate::ErasedInnerMut::Many(mut list) => {
                             let mut idx = 0;
-                            $crate::polonius_loop! { |s| -> Option<&'polonius mut T> {
-                                let Some(field) = s.get_field_mut(idx) else {
+                            $crate::polonius_loop! { |list| -> Option<&'polonius mut T> {
+                                let Some(item) = list.get_item_mut(idx) else {
                                     $crate::polonius_return!(None);
                                 };
-                                if let Some(result) = field.downcast_mut() {
+                                if let Some(result) = item.get_mut() {
                                     $crate::polonius_return!(Some(result));
                                 }
                                 idx += 1;
@@ -172,9 +175,289 @@ macro_rules! impl_meta_any {
                         }
                     }
                 }
+
+                /// Obtain an item as a pointer derived from `this`, if exists.
+                ///
+                /// # Safety
+                ///
+                /// `this` must be valid for reads, the result is valid for writes if `this` is.
+                pub unsafe fn get_ptr<T: 'static>(this: *mut Self) -> Option<*mut T> {
+                    unsafe { (*this).as_erased_ptr(this.cast()) }.get_nth(0)
+                }
+
+                /// Obtain all items of type `T` in lookup order.
+                pub fn get_all<T: 'static>(&self) -> $crate::All<&T> {
+                    $crate::All::from_erased(self.as_erased())
+                }
+
+
+                /// Returns the number of items of type `T`.
+                pub fn count<T: 'static>(&self) -> usize {
+                    self.as_erased().count::<T>()
+                }
+
+                /// Obtain the `n`th item of type `T`, if exists.
+                pub fn get_nth<T: 'static>(&self, n: usize) -> Option<&T> {
+                    self.as_erased().get_nth(n)
+                }
+
+                /// Obtain the `n`th mutable item of type `T`, if exists.
+                pub fn get_nth_mut<T: 'static>(&mut self, n: usize) -> Option<&mut T> {
+                    self.as_erased_mut().get_nth(n)
+                }
+
+                /// Returns `true` if an item of type `T` exists.
+                pub fn contains<T: 'static>(&self) -> bool {
+                    self.get::<T>().is_some()
+                }
+
+                /// Obtain an item, returns [`MetaError::Missing`](crate::MetaError::Missing) if not exists.
+                pub fn try_get<T: 'static>(&self) -> Result<&T, $crate::MetaError> {
+                    self.get().ok_or($crate::MetaError::Missing {
+                        type_name: ::core::any::type_name::<T>(),
+                    })
+                }
+
+                /// Obtain a mutable item, returns [`MetaError::NotMutable`](crate::MetaError::NotMutable)
+                /// if the item is not mutable.
+                pub fn try_get_mut<T: 'static>(&mut self) -> Result<&mut T, $crate::MetaError> {
+                    $crate::try_get_mut_dyn(self)
+                }
+
+                /// Try obtain multiple values from the [`MetaAny`].
+                pub fn query_ref<Q: $crate::MetaQuery>(&self) -> Option<Q::Output<'_>> {
+                    Q::query_dyn_ref(self)
+                }
+
+                /// Try obtain multiple values from the [`MetaAny`].
+                ///
+                /// # Panics
+                ///
+                /// If fields alias, like `(&mut A, &A)`.
+                pub fn query_mut<Q: $crate::MetaQuery>(&mut self) -> Option<Q::Output<'_>> {
+                    Q::query_dyn_mut(self)
+                }
+
+                /// Try obtain multiple values, reports every missing field on failure.
+                pub fn try_query_ref<Q: $crate::MetaQuery>(&self) -> Result<Q::Output<'_>, $crate::MetaError> {
+                    $crate::try_query_ref_dyn::<Q>(self)
+                }
+
+                /// Try obtain multiple values, reports every missing field on failure.
+                ///
+                /// Returns [`MetaError::Aliased`](crate::MetaError::Aliased) if fields alias.
+                pub fn try_query_mut<Q: $crate::MetaQuery>(&mut self) -> Result<Q::Output<'_>, $crate::MetaError> {
+                    $crate::try_query_mut_dyn::<Q>(self)
+                }
+
+                /// Borrow an item stored as `RefCell<T>`, if exists.
+                ///
+                /// # Panics
+                ///
+                /// If the item is currently mutably borrowed.
+                pub fn borrow<T: 'static>(&self) -> Option<::core::cell::Ref<'_, T>> {
+                    self.get::<$crate::RefCell<T>>()
+                        .map($crate::RefCell::borrow)
+                }
+
+                /// Mutably borrow an item stored as `RefCell<T>`, if exists.
+                ///
+                /// # Panics
+                ///
+                /// If the item is currently borrowed.
+                pub fn borrow_mut<T: 'static>(&self) -> Option<::core::cell::RefMut<'_, T>> {
+                    self.get::<$crate::RefCell<T>>()
+                        .map($crate::RefCell::borrow_mut)
+                }
+
+                $crate::__impl_meta_any_std!();
+                $crate::__impl_meta_any_alloc!();
+
+                /// Join with another concrete value.
+                pub fn join<T: 'static>(&mut self, other: T) -> $crate::Join<&mut $crate::DynMetaTuple, $crate::MetaItem<T>> {
+                    $crate::Join($crate::DynMetaTuple::from_mut(self), $crate::MetaItem(other))
+                }
+
+                /// Join with a reference to a concrete value.
+                ///
+                /// If querying for a mutable reference, will return `None`.
+                pub fn join_ref<'t, T: 'static>(&'t mut self, other: &'t T) -> $crate::Join<&'t mut $crate::DynMetaTuple, &'t $crate::MetaItem<T>> {
+                    $crate::Join($crate::DynMetaTuple::from_mut(self), $crate::MetaItem::from_ref(other))
+                }
+
+                /// Join with a mutable reference to a concrete value.
+                pub fn join_mut<'t, T: 'static>(&'t mut self, other: &'t mut T) -> $crate::Join<&'t mut $crate::DynMetaTuple, &'t mut $crate::MetaItem<T>> {
+                    $crate::Join($crate::DynMetaTuple::from_mut(self), $crate::MetaItem::from_mut(other))
+                }
+
+                /// Join with another [`MetaTuple`](crate::MetaTuple).
+                pub fn join_tuple<M: $crate::MetaTuple>(&mut self, other: M) -> $crate::Join<&mut $crate::DynMetaTuple, M> {
+                    $crate::Join($crate::DynMetaTuple::from_mut(self), other)
+                }
+
+                /// Returns `true` if the concrete type is `M`.
+                pub fn is<M: MetaAny + 'static>(&self) -> bool {
+                    self.meta_type_id() == Some(::core::any::TypeId::of::<M>())
+                }
+
+                /// Downcast to the concrete type `M`, if matches.
+                pub fn downcast_ref<M: MetaAny + 'static>(&self) -> Option<&M> {
+                    // Safety:
+                    //
+                    // Safe since `meta_type_id` is the `TypeId` of the concrete type.
+                    self.is::<M>().then(|| unsafe { &*(self as *const Self).cast::<M>() })
+                }
+
+                /// Downcast to the concrete type `M` mutably, if matches.
+                pub fn downcast_mut<M: MetaAny + 'static>(&mut self) -> Option<&mut M> {
+                    // Safety:
+                    //
+                    // Safe since `meta_type_id` is the `TypeId` of the concrete type.
+                    self.is::<M>().then(|| unsafe { &mut *(self as *mut Self).cast::<M>() })
+                }
             }
+
+            $crate::__impl_meta_any_alloc!([$($generics)*] $($bounds)+);
         };
     };
+    ([$($generics: tt)*] $($bounds: tt)+) => {
+        $crate::impl_meta_any!([$($generics)*] dyn $($bounds)+);
+    };
+    (dyn $($bounds: tt)+) => {
+        $crate::impl_meta_any!([] dyn $($bounds)+);
+    };
+    ($($bounds: tt)+) => {
+        $crate::impl_meta_any!([] dyn $($bounds)+);
+    };
+}
+
+/// Lock functions on subtraits of [`MetaAny`], requires the `std` feature.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_meta_any_std {
+    () => {
+        /// Lock an item stored as `Mutex<T>`, if exists.
+        ///
+        /// # Panics
+        ///
+        /// If the lock is poisoned.
+        pub fn lock<T: 'static>(&self) -> Option<$crate::MutexGuard<'_, T>> {
+            self.get::<$crate::Mutex<T>>().map(|x| x.lock().unwrap())
+        }
+
+        /// Lock an item stored as `RwLock<T>` with shared read access, if exists.
+        ///
+        /// # Panics
+        ///
+        /// If the lock is poisoned.
+        pub fn read<T: 'static>(&self) -> Option<$crate::RwLockReadGuard<'_, T>> {
+            self.get::<$crate::RwLock<T>>().map(|x| x.read().unwrap())
+        }
+
+        /// Lock an item stored as `RwLock<T>` with exclusive write access, if exists.
+        ///
+        /// # Panics
+        ///
+        /// If the lock is poisoned.
+        pub fn write<T: 'static>(&self) -> Option<$crate::RwLockWriteGuard<'_, T>> {
+            self.get::<$crate::RwLock<T>>().map(|x| x.write().unwrap())
+        }
+    };
+}
+
+/// Lock functions on subtraits of [`MetaAny`], requires the `std` feature.
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_meta_any_std {
+    () => {};
+}
+
+/// `get_all_mut`, `type_ids`, `split_query_mut` and [`DowncastBox`](crate::DowncastBox)
+/// for subtraits of [`MetaAny`], requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_meta_any_alloc {
+    () => {
+        /// Obtain all mutable items of type `T` in lookup order.
+        pub fn get_all_mut<T: 'static>(&mut self) -> $crate::All<&mut T> {
+            // Safety:
+            //
+            // Safe since `self` is borrowed mutably.
+            unsafe {
+                let this: *mut Self = self;
+                $crate::All::from_erased_ptr((*this).as_erased_ptr(this.cast()))
+            }
+        }
+
+        /// Obtain the [`TypeId`](::core::any::TypeId)s of all items in lookup order.
+        pub fn type_ids(&self) -> impl Iterator<Item = ::core::any::TypeId> {
+            let mut type_ids = $crate::Vec::new();
+            self.visit(&mut |item, _| type_ids.push($crate::Any::type_id(item)));
+            type_ids.into_iter()
+        }
+
+        /// Try obtain multiple values, and a [`Remainder`](crate::Remainder) of the other items.
+        ///
+        /// # Panics
+        ///
+        /// If fields alias, like `(&mut A, &A)`.
+        pub fn split_query_mut<Q: $crate::MetaQuery>(
+            &mut self,
+        ) -> Option<(Q::Output<'_>, $crate::Remainder<'_>)> {
+            $crate::Remainder::split_dyn::<Q>(self)
+        }
+    };
+    ([$($generics: tt)*] $($bounds: tt)+) => {
+        impl<$($generics)*> $crate::DowncastBox for $crate::Box<dyn $($bounds)+ + '_> {
+            fn downcast<M: MetaAny + 'static>(self) -> Result<$crate::Box<M>, Self> {
+                if self.is::<M>() {
+                    // Safety:
+                    //
+                    // Safe since `meta_type_id` is the `TypeId` of the concrete type.
+                    Ok(unsafe { $crate::Box::from_raw($crate::Box::into_raw(self).cast::<M>()) })
+                } else {
+                    Err(self)
+                }
+            }
+        }
+    };
+}
+
+/// `get_all_mut`, `type_ids`, `split_query_mut` and [`DowncastBox`](crate::DowncastBox)
+/// for subtraits of [`MetaAny`], requires the `alloc` feature.
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_meta_any_alloc {
+    ($($tt: tt)*) => {};
+}
+
+/// [`MetaAny::into_sink_boxed`](crate::MetaAny::into_sink_boxed) for implementors of [`MetaTuple`](crate::MetaTuple),
+/// requires the `alloc` feature.
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_into_sink_boxed {
+    () => {
+        fn into_sink_boxed(
+            self: $crate::Box<Self>,
+            sink: &mut dyn FnMut($crate::Box<dyn $crate::Any>, &'static str),
+        ) {
+            $crate::MetaTuple::into_sink(*self, &mut $crate::BoxedSink(sink))
+        }
+    };
+}
+
+/// [`MetaAny::into_sink_boxed`](crate::MetaAny::into_sink_boxed) for implementors of [`MetaTuple`](crate::MetaTuple),
+/// requires the `alloc` feature.
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_into_sink_boxed {
+    () => {};
 }
 
 #[deprecated = "use impl_meta_any"]
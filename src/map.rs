@@ -0,0 +1,291 @@
+use crate::{
+    ErasedBundlePtr, ErasedInner, ErasedInnerMut, ErasedInnerPtr, IntoMetaTuple, MetaAny,
+    MetaBundle, MetaSink, MetaTuple,
+};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, btree_map::Entry},
+    vec::Vec,
+};
+use core::{
+    any::{Any, TypeId, type_name},
+    marker::PhantomData,
+};
+
+/// A heap allocated [`MetaTuple`] that contains at most one item of each type.
+///
+/// Unlike [`Join`](crate::Join) and [`MetaVec`](crate::MetaVec), lookups are `O(log n)`,
+/// including lookups through [`dyn MetaAny`](MetaAny).
+///
+/// ```
+/// let mut map = MetaMap::new();
+/// map.insert(1i32);
+/// assert_eq!(map.insert(2i32), Some(1));
+/// *map.entry::<f32>().or_insert(1.0) += 1.0;
+/// assert_eq!(map.get::<f32>(), Some(&2.0));
+/// ```
+#[derive(Debug, Default)]
+pub struct MetaMap {
+    items: Vec<(Box<dyn Any>, TypeId, &'static str)>,
+    indices: BTreeMap<TypeId, usize>,
+}
+
+impl MetaMap {
+    /// Create an empty [`MetaMap`].
+    pub fn new() -> Self {
+        MetaMap::default()
+    }
+
+    /// Returns the number of items.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if there are no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns `true` if an item of type `T` exists.
+    pub fn contains<T: 'static>(&self) -> bool {
+        self.indices.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Insert an item, returns the previous item of the same type, if exists.
+    pub fn insert<T: 'static>(&mut self, item: T) -> Option<T> {
+        self.insert_boxed(Box::new(item), type_name::<T>())
+            .and_then(|x| x.downcast().ok())
+            .map(|x| *x)
+    }
+
+    fn insert_boxed(
+        &mut self,
+        item: Box<dyn Any>,
+        type_name: &'static str,
+    ) -> Option<Box<dyn Any>> {
+        let type_id = (*item).type_id();
+        match self.indices.get(&type_id) {
+            Some(idx) => {
+                Some(core::mem::replace(&mut self.items[*idx], (item, type_id, type_name)).0)
+            }
+            None => {
+                self.indices.insert(type_id, self.items.len());
+                self.items.push((item, type_id, type_name));
+                None
+            }
+        }
+    }
+
+    /// Remove the item of type `T`, if exists.
+    pub fn remove<T: 'static>(&mut self) -> Option<T> {
+        let idx = self.indices.remove(&TypeId::of::<T>())?;
+        let (item, _, _) = self.items.swap_remove(idx);
+        if let Some((_, moved, _)) = self.items.get(idx) {
+            self.indices.insert(*moved, idx);
+        }
+        item.downcast().ok().map(|x| *x)
+    }
+
+    /// Obtain the entry of type `T` for in-place manipulation.
+    pub fn entry<T: 'static>(&mut self) -> MetaEntry<'_, T> {
+        MetaEntry {
+            entry: self.indices.entry(TypeId::of::<T>()),
+            items: &mut self.items,
+            p: PhantomData,
+        }
+    }
+
+    /// Obtain the item of type `T`, inserts `T::default()` if not exists.
+    pub fn get_or_default<T: Default + 'static>(&mut self) -> &mut T {
+        self.entry().or_default()
+    }
+
+    /// Remove all items.
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.indices.clear();
+    }
+
+    /// Move all owned items of a [`MetaTuple`] into the map.
+    ///
+    /// Existing items of the same type are replaced,
+    /// items joined by reference cannot be moved and are skipped.
+    pub fn extend(&mut self, items: impl IntoMetaTuple) {
+        items.into_meta_tuple().into_sink(self)
+    }
+}
+
+/// An entry of a [`MetaMap`], see [`MetaMap::entry`].
+pub struct MetaEntry<'t, T> {
+    entry: Entry<'t, TypeId, usize>,
+    items: &'t mut Vec<(Box<dyn Any>, TypeId, &'static str)>,
+    p: PhantomData<T>,
+}
+
+impl<'t, T: 'static> MetaEntry<'t, T> {
+    /// Modify the item if exists.
+    pub fn and_modify(self, f: impl FnOnce(&mut T)) -> Self {
+        if let Entry::Occupied(entry) = &self.entry
+            && let Some(item) = self.items[*entry.get()].0.downcast_mut()
+        {
+            f(item)
+        }
+        self
+    }
+
+    /// Obtain the item, inserts `value` if not exists.
+    pub fn or_insert(self, value: T) -> &'t mut T {
+        self.or_insert_with(|| value)
+    }
+
+    /// Obtain the item, inserts the result of `f` if not exists.
+    pub fn or_insert_with(self, f: impl FnOnce() -> T) -> &'t mut T {
+        let idx = match self.entry {
+            Entry::Occupied(entry) => *entry.get(),
+            Entry::Vacant(entry) => {
+                let idx = *entry.insert(self.items.len());
+                self.items
+                    .push((Box::new(f()), TypeId::of::<T>(), type_name::<T>()));
+                idx
+            }
+        };
+        // Items are keyed by their `TypeId`, the downcast cannot fail.
+        self.items[idx].0.downcast_mut().unwrap()
+    }
+
+    /// Obtain the item, inserts `T::default()` if not exists.
+    pub fn or_default(self) -> &'t mut T
+    where
+        T: Default,
+    {
+        self.or_insert_with(T::default)
+    }
+}
+
+impl MetaSink for MetaMap {
+    fn push<T: 'static>(&mut self, item: T) {
+        self.insert(item);
+    }
+
+    fn push_boxed(&mut self, item: Box<dyn Any>, type_name: &'static str) {
+        self.insert_boxed(item, type_name);
+    }
+}
+
+impl MetaBundle for MetaMap {
+    fn get_field(&self, idx: usize) -> Option<&dyn Any> {
+        self.items.get(idx).map(|(x, _, _)| &**x)
+    }
+
+    fn get_field_mut(&mut self, idx: usize) -> Option<&mut dyn Any> {
+        self.items.get_mut(idx).map(|(x, _, _)| &mut **x)
+    }
+
+    fn field_count(&self) -> usize {
+        self.items.len()
+    }
+
+    fn field_type_name(&self, idx: usize) -> Option<&'static str> {
+        self.items.get(idx).map(|(_, _, type_name)| *type_name)
+    }
+
+    fn field_type_id(&self, idx: usize) -> Option<TypeId> {
+        self.items.get(idx).map(|(_, type_id, _)| *type_id)
+    }
+
+    unsafe fn get_field_ptr(this: *mut Self, idx: usize) -> Option<(*mut (), TypeId)> {
+        let (item, type_id, _) = unsafe { &(*this).items }.get(idx)?;
+        Some(((&raw const **item).cast_mut().cast(), *type_id))
+    }
+
+    fn find_field(&self, type_id: TypeId, n: usize) -> Result<usize, usize> {
+        match self.indices.get(&type_id) {
+            Some(idx) if n == 0 => Ok(*idx),
+            Some(_) => Err(1),
+            None => Err(0),
+        }
+    }
+
+    unsafe fn find_field_ptr(this: *mut Self, type_id: TypeId, n: usize) -> Result<usize, usize> {
+        match unsafe { &(*this).indices }.get(&type_id) {
+            Some(idx) if n == 0 => Ok(*idx),
+            Some(_) => Err(1),
+            None => Err(0),
+        }
+    }
+}
+
+unsafe impl MetaAny for MetaMap {
+    fn as_erased(&self) -> ErasedInner<'_> {
+        ErasedInner::Struct(self)
+    }
+
+    fn as_erased_mut(&mut self) -> ErasedInnerMut<'_> {
+        ErasedInnerMut::Struct(self)
+    }
+
+    unsafe fn as_erased_ptr(&self, this: *mut ()) -> ErasedInnerPtr<'_> {
+        unsafe { Self::erase_ptr(this.cast()) }
+    }
+
+    unsafe fn erase_ptr<'t>(this: *mut Self) -> ErasedInnerPtr<'t>
+    where
+        Self: 't,
+    {
+        ErasedInnerPtr::Struct(unsafe { ErasedBundlePtr::new(this) })
+    }
+
+    fn static_type_id() -> Option<TypeId> {
+        Some(TypeId::of::<Self>())
+    }
+
+    fn meta_type_id(&self) -> Option<TypeId> {
+        Self::static_type_id()
+    }
+
+    fn visit(&self, f: &mut dyn FnMut(&dyn Any, &'static str)) {
+        for (item, _, type_name) in &self.items {
+            f(&**item, type_name)
+        }
+    }
+
+    fn visit_mut(&mut self, f: &mut dyn FnMut(&mut dyn Any, &'static str)) {
+        for (item, _, type_name) in &mut self.items {
+            f(&mut **item, type_name)
+        }
+    }
+
+    crate::__impl_into_sink_boxed!();
+}
+
+unsafe impl MetaTuple for MetaMap {
+    fn get<T: 'static>(&self) -> Option<&T> {
+        let idx = self.indices.get(&TypeId::of::<T>())?;
+        self.items[*idx].0.downcast_ref()
+    }
+
+    fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        let idx = self.indices.get(&TypeId::of::<T>())?;
+        self.items[*idx].0.downcast_mut()
+    }
+
+    unsafe fn get_mut_ptr<T: 'static>(this: *mut Self) -> Option<*mut T> {
+        let idx = *unsafe { &(*this).indices }.get(&TypeId::of::<T>())?;
+        let (item, _, _) = &unsafe { &(*this).items }[idx];
+        Some((&raw const **item).cast_mut().cast())
+    }
+
+    fn get_all<T: 'static>(&self) -> impl Iterator<Item = &T> {
+        MetaTuple::get(self).into_iter()
+    }
+
+    fn get_all_mut<T: 'static>(&mut self) -> impl Iterator<Item = &mut T> {
+        MetaTuple::get_mut(self).into_iter()
+    }
+
+    fn into_sink(self, sink: &mut impl MetaSink) {
+        for (item, _, type_name) in self.items {
+            sink.push_boxed(item, type_name)
+        }
+    }
+}
@@ -0,0 +1,243 @@
+//! Composable stages that read from a [`MetaTuple`] and produce a [`MetaTuple`].
+//!
+//! ```
+//! impl MetaStage for Attack {
+//!     type Out = MetaItem<DamageDealt>;
+//!
+//!     fn run<T: MetaTuple + ?Sized>(&self, input: &T) -> Self::Out {
+//!         let attacker = input.get::<Attacker>().unwrap();
+//!         let defender = input.get::<Defender>().unwrap();
+//!         MetaItem(DamageDealt(self.calculate_damage(attacker, defender)))
+//!     }
+//! }
+//!
+//! let card = Attack.then(Heal.when::<Healer>()).and(Draw.repeat(2));
+//! let out = card.run(&meta_tuple!(Attacker, Defender));
+//! ```
+use crate::{
+    ErasedInner, ErasedInnerMut, ErasedInnerPtr, Join, MetaAny, MetaSink, MetaTuple,
+    dynamic::{erased_type_id, project_some},
+};
+use core::{
+    any::{Any, TypeId},
+    marker::PhantomData,
+};
+
+/// A stage of a pipeline, reads from an input [`MetaTuple`] and produces an output [`MetaTuple`].
+pub trait MetaStage {
+    /// The output of this stage.
+    type Out: MetaTuple;
+
+    /// Run this stage.
+    fn run<T: MetaTuple + ?Sized>(&self, input: &T) -> Self::Out;
+
+    /// Run `other` after this stage, `other` can read from both the input and the output of this stage.
+    ///
+    /// Outputs of both stages are joined.
+    fn then<B: MetaStage>(self, other: B) -> Then<Self, B>
+    where
+        Self: Sized,
+    {
+        Then(self, other)
+    }
+
+    /// Run both stages on the same input, outputs of both stages are joined.
+    fn and<B: MetaStage>(self, other: B) -> And<Self, B>
+    where
+        Self: Sized,
+    {
+        And(self, other)
+    }
+
+    /// Run this stage only if the input contains an item of type `T`.
+    fn when<T: 'static>(self) -> When<Self, T>
+    where
+        Self: Sized,
+    {
+        When(self, PhantomData)
+    }
+
+    /// Map the first item of type `T` in the output to `U` and add it to the output.
+    ///
+    /// The original item is kept, the mapped item is looked up before the original output.
+    fn add_mapped<T: 'static, U: 'static>(
+        self,
+        f: impl Fn(&T) -> U,
+    ) -> AddMapped<Self, T, impl Fn(&T) -> U>
+    where
+        Self: Sized,
+    {
+        AddMapped(self, f, PhantomData)
+    }
+
+    /// Run this stage `n` times, each run can read from the output of the previous run,
+    /// which is looked up before the input.
+    ///
+    /// Returns the output of the last run.
+    fn repeat(self, n: usize) -> Repeat<Self>
+    where
+        Self: Sized,
+    {
+        Repeat(self, n)
+    }
+}
+
+impl<S: MetaStage + ?Sized> MetaStage for &S {
+    type Out = S::Out;
+
+    fn run<T: MetaTuple + ?Sized>(&self, input: &T) -> Self::Out {
+        (**self).run(input)
+    }
+}
+
+/// Runs 2 [`MetaStage`]s in sequence, see [`MetaStage::then`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Then<A, B>(pub A, pub B);
+
+impl<A: MetaStage, B: MetaStage> MetaStage for Then<A, B> {
+    type Out = Join<A::Out, B::Out>;
+
+    fn run<T: MetaTuple + ?Sized>(&self, input: &T) -> Self::Out {
+        let out = self.0.run(input);
+        let out_2 = self.1.run(&input.join_tuple(&out));
+        Join(out, out_2)
+    }
+}
+
+/// Runs 2 [`MetaStage`]s on the same input, see [`MetaStage::and`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct And<A, B>(pub A, pub B);
+
+impl<A: MetaStage, B: MetaStage> MetaStage for And<A, B> {
+    type Out = Join<A::Out, B::Out>;
+
+    fn run<T: MetaTuple + ?Sized>(&self, input: &T) -> Self::Out {
+        self.0.run(input).join_tuple(self.1.run(input))
+    }
+}
+
+/// Runs a [`MetaStage`] if an item of type `T` exists, see [`MetaStage::when`].
+pub struct When<S, T>(pub S, PhantomData<fn() -> T>);
+
+impl<S: MetaStage, T: 'static> MetaStage for When<S, T> {
+    type Out = Maybe<S::Out>;
+
+    fn run<I: MetaTuple + ?Sized>(&self, input: &I) -> Self::Out {
+        Maybe(input.contains::<T>().then(|| self.0.run(input)))
+    }
+}
+
+/// Adds a mapped item to the output of a [`MetaStage`], see [`MetaStage::add_mapped`].
+pub struct AddMapped<S, T, F>(pub S, pub F, PhantomData<fn(&T)>);
+
+impl<S: MetaStage, T: 'static, U: 'static, F: Fn(&T) -> U> MetaStage for AddMapped<S, T, F> {
+    type Out = Join<Option<U>, S::Out>;
+
+    fn run<I: MetaTuple + ?Sized>(&self, input: &I) -> Self::Out {
+        let out = self.0.run(input);
+        Join(out.get::<T>().map(&self.1), out)
+    }
+}
+
+/// Runs a [`MetaStage`] multiple times, see [`MetaStage::repeat`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Repeat<S>(pub S, pub usize);
+
+impl<S: MetaStage> MetaStage for Repeat<S> {
+    type Out = Maybe<S::Out>;
+
+    fn run<T: MetaTuple + ?Sized>(&self, input: &T) -> Self::Out {
+        let mut out = Maybe(None);
+        for _ in 0..self.1 {
+            out = Maybe(Some(self.0.run(&(&out).join_tuple(input))));
+        }
+        out
+    }
+}
+
+/// A [`MetaTuple`] that might not exist.
+///
+/// Unlike `Option<T>`, which is a single item, this wraps a [`MetaTuple`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Maybe<M>(pub Option<M>);
+
+unsafe impl<M: MetaTuple> MetaAny for Maybe<M> {
+    fn as_erased(&self) -> ErasedInner<'_> {
+        match &self.0 {
+            Some(value) => value.as_erased(),
+            None => ErasedInner::None,
+        }
+    }
+
+    fn as_erased_mut(&mut self) -> ErasedInnerMut<'_> {
+        match &mut self.0 {
+            Some(value) => value.as_erased_mut(),
+            None => ErasedInnerMut::None,
+        }
+    }
+
+    unsafe fn as_erased_ptr(&self, this: *mut ()) -> ErasedInnerPtr<'_> {
+        unsafe { Self::erase_ptr(this.cast()) }
+    }
+
+    unsafe fn erase_ptr<'t>(this: *mut Self) -> ErasedInnerPtr<'t>
+    where
+        Self: 't,
+    {
+        match unsafe { project_some(&raw mut (*this).0) } {
+            Some(value) => unsafe { M::erase_ptr(value) },
+            None => ErasedInnerPtr::None,
+        }
+    }
+
+    fn static_type_id() -> Option<TypeId> {
+        M::static_type_id()?;
+        Some(erased_type_id::<Self>())
+    }
+
+    fn meta_type_id(&self) -> Option<TypeId> {
+        Self::static_type_id()
+    }
+
+    fn visit(&self, f: &mut dyn FnMut(&dyn Any, &'static str)) {
+        if let Some(value) = &self.0 {
+            value.visit(f)
+        }
+    }
+
+    fn visit_mut(&mut self, f: &mut dyn FnMut(&mut dyn Any, &'static str)) {
+        if let Some(value) = &mut self.0 {
+            value.visit_mut(f)
+        }
+    }
+
+    crate::__impl_into_sink_boxed!();
+}
+
+unsafe impl<M: MetaTuple> MetaTuple for Maybe<M> {
+    fn get<T: 'static>(&self) -> Option<&T> {
+        self.0.as_ref()?.get()
+    }
+
+    fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.0.as_mut()?.get_mut()
+    }
+
+    unsafe fn get_mut_ptr<T: 'static>(this: *mut Self) -> Option<*mut T> {
+        unsafe { M::get_mut_ptr(project_some(&raw mut (*this).0)?) }
+    }
+
+    fn get_all<T: 'static>(&self) -> impl Iterator<Item = &T> {
+        self.0.iter().flat_map(|x| x.get_all())
+    }
+
+    fn get_all_mut<T: 'static>(&mut self) -> impl Iterator<Item = &mut T> {
+        self.0.iter_mut().flat_map(|x| x.get_all_mut())
+    }
+
+    fn into_sink(self, sink: &mut impl MetaSink) {
+        if let Some(value) = self.0 {
+            value.into_sink(sink)
+        }
+    }
+}
@@ -1,21 +1,83 @@
-use crate::{MetaAny, MetaTuple};
-use core::any::{TypeId, type_name};
+#[cfg(feature = "alloc")]
+use crate::ErasedInnerPtr;
+use crate::{ErasedInner, MetaAny, MetaError, MetaTuple};
+#[cfg(feature = "alloc")]
+use alloc::vec::{self, Vec};
+use core::{
+    any::{TypeId, type_name},
+    cell::{Ref, RefCell, RefMut},
+    fmt::{self, Debug},
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    ptr,
+};
 
-pub trait MetaQuerySingle: MetaQuery {
-    fn unique_type_id() -> TypeId;
+/// A query parameter that accesses a single item, like `&A` or `Option<&mut A>`.
+pub trait MetaQuerySingle: MetaQuery {}
 
-    fn compatible<T: MetaQuerySingle>() -> bool {
-        Self::unique_type_id() != T::unique_type_id()
+/// An item accessed by a [`MetaQuery`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Access {
+    /// [`TypeId`] of the accessed item.
+    pub type_id: TypeId,
+    /// Name of the query parameter, like `&mut i32`.
+    pub type_name: &'static str,
+    /// Whether the item is accessed mutably.
+    pub mutable: bool,
+}
+
+impl Access {
+    /// Access of item `A` by query parameter `Q`.
+    pub fn of<Q: ?Sized, A: ?Sized + 'static>(mutable: bool) -> Self {
+        Access {
+            type_id: TypeId::of::<A>(),
+            type_name: type_name::<Q>(),
+            mutable,
+        }
+    }
+
+    /// Returns true if both access the same item and at least one of them is mutable.
+    pub fn conflicts(&self, other: &Access) -> bool {
+        self.type_id == other.type_id && (self.mutable || other.mutable)
     }
 }
 
+/// Returns the first pair of conflicting accesses between `A` and `B` as [`MetaError::Aliased`].
+#[doc(hidden)]
+pub fn conflict<A: MetaQuery + ?Sized, B: MetaQuery + ?Sized>() -> Option<MetaError> {
+    let mut result = None;
+    A::access(&mut |a| {
+        B::access(&mut |b| {
+            if result.is_none() && a.conflicts(&b) {
+                result = Some(MetaError::Aliased {
+                    a: a.type_name,
+                    b: b.type_name,
+                })
+            }
+        })
+    });
+    result
+}
+
 /// Query into a [`MetaTuple`].
 ///
 /// By default implemented on tuples like `(&i32, &String)`.
 ///
+/// Queries nest, `(MyQuery<'_>, &f32)` and derived queries with query fields are queries.
+///
+/// # Aliasing
+///
+/// Items reported by `access` may be read by any number of parameters,
+/// but an item accessed mutably must not be accessed by any other parameter.
+///
+/// Derived queries with fields like `&'t i32` and `&'t mut i32` fail to compile,
+/// tuple queries and generic fields are checked by `validate` at runtime,
+/// since types cannot be compared in const contexts.
+///
 /// # Safety
 ///
-/// `validate` must ensure no duplicate fields are queried.
+/// `access` must visit every item the query accesses,
+/// `validate` must ensure no item accessed mutably is accessed twice.
 pub unsafe trait MetaQuery {
     type Output<'t>: Sized;
     type OutputPtr<'t>: Sized;
@@ -25,8 +87,13 @@ pub unsafe trait MetaQuery {
         if !Self::validate() {
             panic!("{} is not a valid MetaQuery.", type_name::<Self>());
         }
-        let input = Self::query_mut_ptr(input)?;
-        Some(unsafe { Self::from_ptr(input) })
+        // Safety:
+        //
+        // Safe since `input` is borrowed mutably for `'t` and `validate` ensures no aliasing.
+        unsafe {
+            let input = Self::query_mut_ptr(input)?;
+            Some(Self::from_ptr(input))
+        }
     }
 
     fn query_dyn_ref<'t>(input: &'t dyn MetaAny) -> Option<Self::Output<'t>>;
@@ -34,18 +101,67 @@ pub unsafe trait MetaQuery {
         if !Self::validate() {
             panic!("{} is not a valid MetaQuery.", type_name::<Self>());
         }
-        let input = Self::query_dyn_mut_ptr(input)?;
-        Some(unsafe { Self::from_ptr(input) })
+        // Safety:
+        //
+        // Safe since `input` is borrowed mutably for `'t` and `validate` ensures no aliasing.
+        unsafe {
+            let input = Self::query_dyn_mut_ptr(input)?;
+            Some(Self::from_ptr(input))
+        }
     }
 
-    fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(input: &'t T) -> Option<Self::OutputPtr<'t>>;
-    fn query_dyn_mut_ptr<'t>(input: &'t dyn MetaAny) -> Option<Self::OutputPtr<'t>>;
+    /// Query pointers derived from `input`, references are only created by `from_ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `input` must be valid for reads for `'t`, the result is valid for writes if `input` is.
+    unsafe fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(
+        input: *mut T,
+    ) -> Option<Self::OutputPtr<'t>>;
+
+    /// Query pointers derived from `input`, references are only created by `from_ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `input` must be valid for reads for `'t`, the result is valid for writes if `input` is.
+    unsafe fn query_dyn_mut_ptr<'t>(input: *mut (dyn MetaAny + 't)) -> Option<Self::OutputPtr<'t>>;
     /// # Safety
     ///
     /// Input must point to valid data.
     unsafe fn from_ptr<'t>(ptr: Self::OutputPtr<'t>) -> Self::Output<'t>;
 
     fn validate() -> bool;
+
+    /// Visit every item accessed by the query.
+    fn access(visit: &mut dyn FnMut(Access));
+
+    /// Returns the first pair of aliasing fields as [`MetaError::Aliased`], if exists.
+    fn aliased() -> Option<MetaError> {
+        (!Self::validate()).then(|| MetaError::Aliased {
+            a: type_name::<Self>(),
+            b: type_name::<Self>(),
+        })
+    }
+
+    /// Report every error that causes `query_ref`, or `query_mut` if `mutable`, to fail.
+    ///
+    /// By default reports the entire query as missing.
+    fn report<T: MetaTuple + ?Sized>(input: &T, mutable: bool, report: &mut dyn FnMut(MetaError)) {
+        let _ = (input, mutable);
+        report(MetaError::Missing {
+            type_name: type_name::<Self>(),
+        })
+    }
+
+    /// Report every error that causes `query_dyn_ref`, or `query_dyn_mut` if `mutable`, to fail.
+    ///
+    /// By default reports the entire query as missing.
+    fn report_dyn(input: &dyn MetaAny, mutable: bool, report: &mut dyn FnMut(MetaError)) {
+        let _ = (input, mutable);
+        report(MetaError::Missing {
+            type_name: type_name::<Self>(),
+        })
+    }
 }
 
 unsafe impl<A: 'static> MetaQuery for &A {
@@ -73,25 +189,39 @@ unsafe impl<A: 'static> MetaQuery for &A {
         ptr
     }
 
-    fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(input: &'t T) -> Option<Self::OutputPtr<'t>> {
-        (input as &T).get()
+    unsafe fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(
+        input: *mut T,
+    ) -> Option<Self::OutputPtr<'t>> {
+        unsafe { &*input }.get()
     }
 
-    fn query_dyn_mut_ptr<'t>(input: &'t dyn MetaAny) -> Option<Self::OutputPtr<'t>> {
-        input.get()
+    unsafe fn query_dyn_mut_ptr<'t>(input: *mut (dyn MetaAny + 't)) -> Option<Self::OutputPtr<'t>> {
+        unsafe { &*input }.get()
     }
 
     fn validate() -> bool {
         true
     }
-}
 
-impl<A: 'static> MetaQuerySingle for &A {
-    fn unique_type_id() -> TypeId {
-        TypeId::of::<A>()
+    fn access(visit: &mut dyn FnMut(Access)) {
+        visit(Access::of::<Self, A>(false))
+    }
+
+    fn report<T: MetaTuple + ?Sized>(input: &T, _: bool, report: &mut dyn FnMut(MetaError)) {
+        if !input.contains::<A>() {
+            report(MetaError::not_found::<A>(false))
+        }
+    }
+
+    fn report_dyn(input: &dyn MetaAny, _: bool, report: &mut dyn FnMut(MetaError)) {
+        if !input.contains::<A>() {
+            report(MetaError::not_found::<A>(false))
+        }
     }
 }
 
+impl<A: 'static> MetaQuerySingle for &A {}
+
 unsafe impl<A: 'static> MetaQuery for &mut A {
     type Output<'t> = &'t mut A;
 
@@ -117,25 +247,41 @@ unsafe impl<A: 'static> MetaQuery for &mut A {
         unsafe { ptr.as_mut().unwrap() }
     }
 
-    fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(input: &'t T) -> Option<Self::OutputPtr<'t>> {
-        input.get_mut_ptr()
+    unsafe fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(
+        input: *mut T,
+    ) -> Option<Self::OutputPtr<'t>> {
+        unsafe { T::get_mut_ptr(input) }
     }
 
-    fn query_dyn_mut_ptr<'t>(input: &'t dyn MetaAny) -> Option<Self::OutputPtr<'t>> {
-        input.get_ptr()
+    unsafe fn query_dyn_mut_ptr<'t>(input: *mut (dyn MetaAny + 't)) -> Option<Self::OutputPtr<'t>> {
+        unsafe { <dyn MetaAny>::get_ptr(input) }
     }
 
     fn validate() -> bool {
         true
     }
-}
 
-impl<A: 'static> MetaQuerySingle for &mut A {
-    fn unique_type_id() -> TypeId {
-        TypeId::of::<A>()
+    fn access(visit: &mut dyn FnMut(Access)) {
+        visit(Access::of::<Self, A>(true))
+    }
+
+    fn report<T: MetaTuple + ?Sized>(input: &T, mutable: bool, report: &mut dyn FnMut(MetaError)) {
+        if !mutable || unsafe { T::get_mut_ptr::<A>(ptr::from_ref(input).cast_mut()) }.is_none() {
+            report(MetaError::not_found::<A>(input.contains::<A>()))
+        }
+    }
+
+    fn report_dyn(input: &dyn MetaAny, mutable: bool, report: &mut dyn FnMut(MetaError)) {
+        if !mutable
+            || unsafe { <dyn MetaAny>::get_ptr::<A>(ptr::from_ref(input).cast_mut()) }.is_none()
+        {
+            report(MetaError::not_found::<A>(input.contains::<A>()))
+        }
     }
 }
 
+impl<A: 'static> MetaQuerySingle for &mut A {}
+
 unsafe impl<A: 'static> MetaQuery for Option<&A> {
     type Output<'t> = Option<&'t A>;
 
@@ -161,25 +307,31 @@ unsafe impl<A: 'static> MetaQuery for Option<&A> {
         ptr
     }
 
-    fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(input: &'t T) -> Option<Self::OutputPtr<'t>> {
-        Some((input as &T).get())
+    unsafe fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(
+        input: *mut T,
+    ) -> Option<Self::OutputPtr<'t>> {
+        Some(unsafe { &*input }.get())
     }
 
-    fn query_dyn_mut_ptr<'t>(input: &'t dyn MetaAny) -> Option<Self::OutputPtr<'t>> {
-        Some(input.get())
+    unsafe fn query_dyn_mut_ptr<'t>(input: *mut (dyn MetaAny + 't)) -> Option<Self::OutputPtr<'t>> {
+        Some(unsafe { &*input }.get())
     }
 
     fn validate() -> bool {
         true
     }
-}
 
-impl<A: 'static> MetaQuerySingle for Option<&A> {
-    fn unique_type_id() -> TypeId {
-        TypeId::of::<A>()
+    fn access(visit: &mut dyn FnMut(Access)) {
+        visit(Access::of::<Self, A>(false))
     }
+
+    fn report<T: MetaTuple + ?Sized>(_: &T, _: bool, _: &mut dyn FnMut(MetaError)) {}
+
+    fn report_dyn(_: &dyn MetaAny, _: bool, _: &mut dyn FnMut(MetaError)) {}
 }
 
+impl<A: 'static> MetaQuerySingle for Option<&A> {}
+
 unsafe impl<A: 'static> MetaQuery for Option<&mut A> {
     type Output<'t> = Option<&'t mut A>;
 
@@ -205,36 +357,903 @@ unsafe impl<A: 'static> MetaQuery for Option<&mut A> {
         ptr.map(|ptr| unsafe { ptr.as_mut().unwrap() })
     }
 
-    fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(input: &'t T) -> Option<Self::OutputPtr<'t>> {
-        Some(input.get_mut_ptr())
+    unsafe fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(
+        input: *mut T,
+    ) -> Option<Self::OutputPtr<'t>> {
+        Some(unsafe { T::get_mut_ptr(input) })
     }
 
-    fn query_dyn_mut_ptr<'t>(input: &'t dyn MetaAny) -> Option<Self::OutputPtr<'t>> {
-        Some(input.get_ptr())
+    unsafe fn query_dyn_mut_ptr<'t>(input: *mut (dyn MetaAny + 't)) -> Option<Self::OutputPtr<'t>> {
+        Some(unsafe { <dyn MetaAny>::get_ptr(input) })
     }
 
     fn validate() -> bool {
         true
     }
+
+    fn access(visit: &mut dyn FnMut(Access)) {
+        visit(Access::of::<Self, A>(true))
+    }
+
+    fn report<T: MetaTuple + ?Sized>(_: &T, _: bool, _: &mut dyn FnMut(MetaError)) {}
+
+    fn report_dyn(_: &dyn MetaAny, _: bool, _: &mut dyn FnMut(MetaError)) {}
 }
 
-impl<A: 'static> MetaQuerySingle for Option<&mut A> {
-    fn unique_type_id() -> TypeId {
-        TypeId::of::<A>()
+impl<A: 'static> MetaQuerySingle for Option<&mut A> {}
+
+/// Borrows an item stored as `RefCell<A>`.
+///
+/// Fails if the item does not exist or is currently mutably borrowed.
+unsafe impl<A: 'static> MetaQuery for Ref<'_, A> {
+    type Output<'t> = Ref<'t, A>;
+
+    fn query_ref<'t, T: MetaTuple + ?Sized + 't>(input: &'t T) -> Option<Self::Output<'t>> {
+        input.get::<RefCell<A>>()?.try_borrow().ok()
+    }
+
+    fn query_dyn_ref<'t>(input: &'t dyn MetaAny) -> Option<Self::Output<'t>> {
+        input.get::<RefCell<A>>()?.try_borrow().ok()
+    }
+
+    type OutputPtr<'t> = Ref<'t, A>;
+
+    unsafe fn from_ptr<'t>(ptr: Self::OutputPtr<'t>) -> Self::Output<'t> {
+        ptr
+    }
+
+    unsafe fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(
+        input: *mut T,
+    ) -> Option<Self::OutputPtr<'t>> {
+        Self::query_ref(unsafe { &*input })
+    }
+
+    unsafe fn query_dyn_mut_ptr<'t>(input: *mut (dyn MetaAny + 't)) -> Option<Self::OutputPtr<'t>> {
+        Self::query_dyn_ref(unsafe { &*input })
+    }
+
+    fn validate() -> bool {
+        true
+    }
+
+    fn access(visit: &mut dyn FnMut(Access)) {
+        visit(Access::of::<Self, RefCell<A>>(false))
+    }
+
+    fn report<T: MetaTuple + ?Sized>(input: &T, _: bool, report: &mut dyn FnMut(MetaError)) {
+        match input.get::<RefCell<A>>() {
+            Some(cell) => {
+                if cell.try_borrow().is_err() {
+                    report(MetaError::Aliased {
+                        a: type_name::<Self>(),
+                        b: type_name::<RefCell<A>>(),
+                    })
+                }
+            }
+            None => report(MetaError::not_found::<RefCell<A>>(false)),
+        }
+    }
+
+    fn report_dyn(input: &dyn MetaAny, _: bool, report: &mut dyn FnMut(MetaError)) {
+        match input.get::<RefCell<A>>() {
+            Some(cell) => {
+                if cell.try_borrow().is_err() {
+                    report(MetaError::Aliased {
+                        a: type_name::<Self>(),
+                        b: type_name::<RefCell<A>>(),
+                    })
+                }
+            }
+            None => report(MetaError::not_found::<RefCell<A>>(false)),
+        }
     }
 }
 
-macro_rules! validate {
-    () => { true };
+impl<A: 'static> MetaQuerySingle for Ref<'_, A> {}
+
+/// Mutably borrows an item stored as `RefCell<A>`, this does not require mutable access to the [`MetaTuple`].
+///
+/// Fails if the item does not exist or is currently borrowed.
+unsafe impl<A: 'static> MetaQuery for RefMut<'_, A> {
+    type Output<'t> = RefMut<'t, A>;
+
+    fn query_ref<'t, T: MetaTuple + ?Sized + 't>(input: &'t T) -> Option<Self::Output<'t>> {
+        input.get::<RefCell<A>>()?.try_borrow_mut().ok()
+    }
+
+    fn query_dyn_ref<'t>(input: &'t dyn MetaAny) -> Option<Self::Output<'t>> {
+        input.get::<RefCell<A>>()?.try_borrow_mut().ok()
+    }
+
+    type OutputPtr<'t> = RefMut<'t, A>;
+
+    unsafe fn from_ptr<'t>(ptr: Self::OutputPtr<'t>) -> Self::Output<'t> {
+        ptr
+    }
+
+    unsafe fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(
+        input: *mut T,
+    ) -> Option<Self::OutputPtr<'t>> {
+        Self::query_ref(unsafe { &*input })
+    }
+
+    unsafe fn query_dyn_mut_ptr<'t>(input: *mut (dyn MetaAny + 't)) -> Option<Self::OutputPtr<'t>> {
+        Self::query_dyn_ref(unsafe { &*input })
+    }
+
+    fn validate() -> bool {
+        true
+    }
+
+    fn access(visit: &mut dyn FnMut(Access)) {
+        visit(Access::of::<Self, RefCell<A>>(true))
+    }
+
+    fn report<T: MetaTuple + ?Sized>(input: &T, _: bool, report: &mut dyn FnMut(MetaError)) {
+        match input.get::<RefCell<A>>() {
+            Some(cell) => {
+                if cell.try_borrow_mut().is_err() {
+                    report(MetaError::Aliased {
+                        a: type_name::<Self>(),
+                        b: type_name::<RefCell<A>>(),
+                    })
+                }
+            }
+            None => report(MetaError::not_found::<RefCell<A>>(false)),
+        }
+    }
+
+    fn report_dyn(input: &dyn MetaAny, _: bool, report: &mut dyn FnMut(MetaError)) {
+        match input.get::<RefCell<A>>() {
+            Some(cell) => {
+                if cell.try_borrow_mut().is_err() {
+                    report(MetaError::Aliased {
+                        a: type_name::<Self>(),
+                        b: type_name::<RefCell<A>>(),
+                    })
+                }
+            }
+            None => report(MetaError::not_found::<RefCell<A>>(false)),
+        }
+    }
+}
+
+impl<A: 'static> MetaQuerySingle for RefMut<'_, A> {}
+
+/// Storage of [`All`].
+#[doc(hidden)]
+pub trait AllQuery {
+    type Erased;
+}
+
+impl<'t, A: 'static> AllQuery for &'t A {
+    type Erased = ErasedInner<'t>;
+}
+
+#[cfg(feature = "alloc")]
+impl<A: 'static> AllQuery for &mut A {
+    type Erased = vec::IntoIter<*mut A>;
+}
+
+/// A [`MetaQuery`] parameter that yields every item of a type, as `All<&A>` or `All<&mut A>`.
+///
+/// This is also an [`Iterator`] over the items.
+///
+/// `All<&mut A>` requires the `alloc` feature, since every item is found when created,
+/// looking up items after some are yielded could invalidate them.
+///
+/// ```
+/// let tuple = meta_tuple!(1i32, 2i32, 'c');
+/// let (all, c) = tuple.query_ref::<(All<&i32>, &char)>().unwrap();
+/// assert_eq!(all.copied().sum::<i32>(), 3);
+/// ```
+pub struct All<Q: AllQuery> {
+    erased: Q::Erased,
+    index: usize,
+    p: PhantomData<Q>,
+}
+
+impl<'t, A: 'static> All<&'t A> {
+    /// Iterate over every item of type `A` in an erased [`MetaTuple`].
+    pub fn from_erased(erased: ErasedInner<'t>) -> Self {
+        All {
+            erased,
+            index: 0,
+            p: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'t, A: 'static> All<&'t mut A> {
+    /// Iterate over every item of type `A` in an erased [`MetaTuple`].
+    ///
+    /// # Safety
+    ///
+    /// `erased` must be obtained from an exclusive borrow that lives for `'t`.
+    pub unsafe fn from_erased_ptr(erased: ErasedInnerPtr<'t>) -> Self {
+        let mut ptrs = Vec::new();
+        erased.visit_ptr(&mut |ptr| ptrs.push(ptr));
+        All {
+            erased: ptrs.into_iter(),
+            index: 0,
+            p: PhantomData,
+        }
+    }
+}
+
+impl<'t, A: 'static> Iterator for All<&'t A> {
+    type Item = &'t A;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = self.erased.get_nth(self.index)?;
+        self.index += 1;
+        Some(result)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'t, A: 'static> Iterator for All<&'t mut A> {
+    type Item = &'t mut A;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = self.erased.next()?;
+        // Safety:
+        //
+        // Safe since the items are obtained from an exclusive borrow and never yielded twice.
+        Some(unsafe { result.as_mut() }.unwrap())
+    }
+}
+
+unsafe impl<A: 'static> MetaQuery for All<&A> {
+    type Output<'t> = All<&'t A>;
+
+    fn query_ref<'t, T: MetaTuple + ?Sized + 't>(input: &'t T) -> Option<Self::Output<'t>> {
+        Some(All::from_erased(input.as_erased()))
+    }
+
+    fn query_dyn_ref<'t>(input: &'t dyn MetaAny) -> Option<Self::Output<'t>> {
+        Some(All::from_erased(input.as_erased()))
+    }
+
+    type OutputPtr<'t> = All<&'t A>;
+
+    unsafe fn from_ptr<'t>(ptr: Self::OutputPtr<'t>) -> Self::Output<'t> {
+        ptr
+    }
+
+    unsafe fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(
+        input: *mut T,
+    ) -> Option<Self::OutputPtr<'t>> {
+        Some(All::from_erased(unsafe { &*input }.as_erased()))
+    }
+
+    unsafe fn query_dyn_mut_ptr<'t>(input: *mut (dyn MetaAny + 't)) -> Option<Self::OutputPtr<'t>> {
+        Some(All::from_erased(unsafe { &*input }.as_erased()))
+    }
+
+    fn validate() -> bool {
+        true
+    }
+
+    fn access(visit: &mut dyn FnMut(Access)) {
+        visit(Access::of::<Self, A>(false))
+    }
+
+    fn report<T: MetaTuple + ?Sized>(_: &T, _: bool, _: &mut dyn FnMut(MetaError)) {}
+
+    fn report_dyn(_: &dyn MetaAny, _: bool, _: &mut dyn FnMut(MetaError)) {}
+}
+
+impl<A: 'static> MetaQuerySingle for All<&A> {}
+
+#[cfg(feature = "alloc")]
+unsafe impl<A: 'static> MetaQuery for All<&mut A> {
+    type Output<'t> = All<&'t mut A>;
+
+    fn query_ref<'t, T: MetaTuple + ?Sized + 't>(_: &'t T) -> Option<Self::Output<'t>> {
+        None
+    }
+
+    fn query_dyn_ref<'t>(_: &'t dyn MetaAny) -> Option<Self::Output<'t>> {
+        None
+    }
+
+    type OutputPtr<'t> = All<&'t mut A>;
+
+    unsafe fn from_ptr<'t>(ptr: Self::OutputPtr<'t>) -> Self::Output<'t> {
+        ptr
+    }
+
+    unsafe fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(
+        input: *mut T,
+    ) -> Option<Self::OutputPtr<'t>> {
+        Some(unsafe { All::from_erased_ptr((*input).as_erased_ptr(input.cast())) })
+    }
+
+    unsafe fn query_dyn_mut_ptr<'t>(input: *mut (dyn MetaAny + 't)) -> Option<Self::OutputPtr<'t>> {
+        Some(unsafe { All::from_erased_ptr((*input).as_erased_ptr(input.cast())) })
+    }
+
+    fn validate() -> bool {
+        true
+    }
+
+    fn access(visit: &mut dyn FnMut(Access)) {
+        visit(Access::of::<Self, A>(true))
+    }
+
+    fn report<T: MetaTuple + ?Sized>(_: &T, mutable: bool, report: &mut dyn FnMut(MetaError)) {
+        if !mutable {
+            report(MetaError::not_found::<A>(true))
+        }
+    }
+
+    fn report_dyn(_: &dyn MetaAny, mutable: bool, report: &mut dyn FnMut(MetaError)) {
+        if !mutable {
+            report(MetaError::not_found::<A>(true))
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A: 'static> MetaQuerySingle for All<&mut A> {}
+
+/// A [`MetaQuery`] parameter that yields a clone of an item.
+///
+/// Like all query parameters, this is also the output type, and dereferences to the item.
+///
+/// ```
+/// let tuple = meta_tuple!(String::from("a"), 1i32);
+/// let (Cloned(string), Copied(int)) = tuple.query_ref::<(Cloned<String>, Copied<i32>)>().unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Cloned<A>(pub A);
+
+unsafe impl<A: Clone + 'static> MetaQuery for Cloned<A> {
+    type Output<'t> = Self;
+
+    fn query_ref<'t, T: MetaTuple + ?Sized + 't>(input: &'t T) -> Option<Self::Output<'t>> {
+        input.get::<A>().cloned().map(Cloned)
+    }
+
+    fn query_dyn_ref<'t>(input: &'t dyn MetaAny) -> Option<Self::Output<'t>> {
+        input.get::<A>().cloned().map(Cloned)
+    }
+
+    type OutputPtr<'t> = Self;
+
+    unsafe fn from_ptr<'t>(ptr: Self::OutputPtr<'t>) -> Self::Output<'t> {
+        ptr
+    }
+
+    unsafe fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(
+        input: *mut T,
+    ) -> Option<Self::OutputPtr<'t>> {
+        Self::query_ref(unsafe { &*input })
+    }
+
+    unsafe fn query_dyn_mut_ptr<'t>(input: *mut (dyn MetaAny + 't)) -> Option<Self::OutputPtr<'t>> {
+        Self::query_dyn_ref(unsafe { &*input })
+    }
+
+    fn validate() -> bool {
+        true
+    }
+
+    fn access(visit: &mut dyn FnMut(Access)) {
+        visit(Access::of::<Self, A>(false))
+    }
+
+    fn report<T: MetaTuple + ?Sized>(input: &T, _: bool, report: &mut dyn FnMut(MetaError)) {
+        if !input.contains::<A>() {
+            report(MetaError::not_found::<A>(false))
+        }
+    }
+
+    fn report_dyn(input: &dyn MetaAny, _: bool, report: &mut dyn FnMut(MetaError)) {
+        if !input.contains::<A>() {
+            report(MetaError::not_found::<A>(false))
+        }
+    }
+}
+
+impl<A: Clone + 'static> MetaQuerySingle for Cloned<A> {}
+
+impl<A> Deref for Cloned<A> {
+    type Target = A;
+
+    fn deref(&self) -> &A {
+        &self.0
+    }
+}
+
+impl<A> DerefMut for Cloned<A> {
+    fn deref_mut(&mut self) -> &mut A {
+        &mut self.0
+    }
+}
+
+/// A [`MetaQuery`] parameter that yields a copy of an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Copied<A>(pub A);
+
+unsafe impl<A: Copy + 'static> MetaQuery for Copied<A> {
+    type Output<'t> = Self;
+
+    fn query_ref<'t, T: MetaTuple + ?Sized + 't>(input: &'t T) -> Option<Self::Output<'t>> {
+        input.get::<A>().copied().map(Copied)
+    }
+
+    fn query_dyn_ref<'t>(input: &'t dyn MetaAny) -> Option<Self::Output<'t>> {
+        input.get::<A>().copied().map(Copied)
+    }
+
+    type OutputPtr<'t> = Self;
+
+    unsafe fn from_ptr<'t>(ptr: Self::OutputPtr<'t>) -> Self::Output<'t> {
+        ptr
+    }
+
+    unsafe fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(
+        input: *mut T,
+    ) -> Option<Self::OutputPtr<'t>> {
+        Self::query_ref(unsafe { &*input })
+    }
+
+    unsafe fn query_dyn_mut_ptr<'t>(input: *mut (dyn MetaAny + 't)) -> Option<Self::OutputPtr<'t>> {
+        Self::query_dyn_ref(unsafe { &*input })
+    }
+
+    fn validate() -> bool {
+        true
+    }
+
+    fn access(visit: &mut dyn FnMut(Access)) {
+        visit(Access::of::<Self, A>(false))
+    }
+
+    fn report<T: MetaTuple + ?Sized>(input: &T, _: bool, report: &mut dyn FnMut(MetaError)) {
+        if !input.contains::<A>() {
+            report(MetaError::not_found::<A>(false))
+        }
+    }
+
+    fn report_dyn(input: &dyn MetaAny, _: bool, report: &mut dyn FnMut(MetaError)) {
+        if !input.contains::<A>() {
+            report(MetaError::not_found::<A>(false))
+        }
+    }
+}
+
+impl<A: Copy + 'static> MetaQuerySingle for Copied<A> {}
+
+impl<A> Deref for Copied<A> {
+    type Target = A;
+
+    fn deref(&self) -> &A {
+        &self.0
+    }
+}
+
+impl<A> DerefMut for Copied<A> {
+    fn deref_mut(&mut self) -> &mut A {
+        &mut self.0
+    }
+}
+
+/// A [`MetaQuery`] parameter that yields a clone of an item, or `A::default()` if it does not exist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct OrDefault<A>(pub A);
+
+unsafe impl<A: Clone + Default + 'static> MetaQuery for OrDefault<A> {
+    type Output<'t> = Self;
+
+    fn query_ref<'t, T: MetaTuple + ?Sized + 't>(input: &'t T) -> Option<Self::Output<'t>> {
+        Some(OrDefault(input.get::<A>().cloned().unwrap_or_default()))
+    }
+
+    fn query_dyn_ref<'t>(input: &'t dyn MetaAny) -> Option<Self::Output<'t>> {
+        Some(OrDefault(input.get::<A>().cloned().unwrap_or_default()))
+    }
+
+    type OutputPtr<'t> = Self;
+
+    unsafe fn from_ptr<'t>(ptr: Self::OutputPtr<'t>) -> Self::Output<'t> {
+        ptr
+    }
+
+    unsafe fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(
+        input: *mut T,
+    ) -> Option<Self::OutputPtr<'t>> {
+        Self::query_ref(unsafe { &*input })
+    }
+
+    unsafe fn query_dyn_mut_ptr<'t>(input: *mut (dyn MetaAny + 't)) -> Option<Self::OutputPtr<'t>> {
+        Self::query_dyn_ref(unsafe { &*input })
+    }
+
+    fn validate() -> bool {
+        true
+    }
+
+    fn access(visit: &mut dyn FnMut(Access)) {
+        visit(Access::of::<Self, A>(false))
+    }
+
+    fn report<T: MetaTuple + ?Sized>(_: &T, _: bool, _: &mut dyn FnMut(MetaError)) {}
+
+    fn report_dyn(_: &dyn MetaAny, _: bool, _: &mut dyn FnMut(MetaError)) {}
+}
+
+impl<A: Clone + Default + 'static> MetaQuerySingle for OrDefault<A> {}
+
+impl<A> Deref for OrDefault<A> {
+    type Target = A;
+
+    fn deref(&self) -> &A {
+        &self.0
+    }
+}
+
+impl<A> DerefMut for OrDefault<A> {
+    fn deref_mut(&mut self) -> &mut A {
+        &mut self.0
+    }
+}
+
+/// A [`MetaQuery`] filter that requires an item to exist.
+///
+/// The item is not accessed, so `(Has<A>, &mut A)` is valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Has<A: ?Sized>(PhantomData<fn() -> A>);
+
+unsafe impl<A: 'static> MetaQuery for Has<A> {
+    type Output<'t> = Self;
+
+    fn query_ref<'t, T: MetaTuple + ?Sized + 't>(input: &'t T) -> Option<Self::Output<'t>> {
+        input.contains::<A>().then_some(Has(PhantomData))
+    }
+
+    fn query_dyn_ref<'t>(input: &'t dyn MetaAny) -> Option<Self::Output<'t>> {
+        input.contains::<A>().then_some(Has(PhantomData))
+    }
+
+    type OutputPtr<'t> = Self;
+
+    unsafe fn from_ptr<'t>(ptr: Self::OutputPtr<'t>) -> Self::Output<'t> {
+        ptr
+    }
+
+    unsafe fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(
+        input: *mut T,
+    ) -> Option<Self::OutputPtr<'t>> {
+        Self::query_ref(unsafe { &*input })
+    }
+
+    unsafe fn query_dyn_mut_ptr<'t>(input: *mut (dyn MetaAny + 't)) -> Option<Self::OutputPtr<'t>> {
+        Self::query_dyn_ref(unsafe { &*input })
+    }
+
+    fn validate() -> bool {
+        true
+    }
+
+    fn access(_: &mut dyn FnMut(Access)) {}
+
+    fn report<T: MetaTuple + ?Sized>(input: &T, _: bool, report: &mut dyn FnMut(MetaError)) {
+        if !input.contains::<A>() {
+            report(MetaError::not_found::<A>(false))
+        }
+    }
+
+    fn report_dyn(input: &dyn MetaAny, _: bool, report: &mut dyn FnMut(MetaError)) {
+        if !input.contains::<A>() {
+            report(MetaError::not_found::<A>(false))
+        }
+    }
+}
+
+impl<A: 'static> MetaQuerySingle for Has<A> {}
+
+/// A [`MetaQuery`] filter that requires an item to not exist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Without<A: ?Sized>(PhantomData<fn() -> A>);
+
+unsafe impl<A: 'static> MetaQuery for Without<A> {
+    type Output<'t> = Self;
+
+    fn query_ref<'t, T: MetaTuple + ?Sized + 't>(input: &'t T) -> Option<Self::Output<'t>> {
+        (!input.contains::<A>()).then_some(Without(PhantomData))
+    }
+
+    fn query_dyn_ref<'t>(input: &'t dyn MetaAny) -> Option<Self::Output<'t>> {
+        (!input.contains::<A>()).then_some(Without(PhantomData))
+    }
+
+    type OutputPtr<'t> = Self;
+
+    unsafe fn from_ptr<'t>(ptr: Self::OutputPtr<'t>) -> Self::Output<'t> {
+        ptr
+    }
+
+    unsafe fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(
+        input: *mut T,
+    ) -> Option<Self::OutputPtr<'t>> {
+        Self::query_ref(unsafe { &*input })
+    }
+
+    unsafe fn query_dyn_mut_ptr<'t>(input: *mut (dyn MetaAny + 't)) -> Option<Self::OutputPtr<'t>> {
+        Self::query_dyn_ref(unsafe { &*input })
+    }
+
+    fn validate() -> bool {
+        true
+    }
+
+    fn access(_: &mut dyn FnMut(Access)) {}
+
+    fn report<T: MetaTuple + ?Sized>(input: &T, _: bool, report: &mut dyn FnMut(MetaError)) {
+        if input.contains::<A>() {
+            report(MetaError::Unexpected {
+                type_name: type_name::<A>(),
+            })
+        }
+    }
+
+    fn report_dyn(input: &dyn MetaAny, _: bool, report: &mut dyn FnMut(MetaError)) {
+        if input.contains::<A>() {
+            report(MetaError::Unexpected {
+                type_name: type_name::<A>(),
+            })
+        }
+    }
+}
+
+impl<A: 'static> MetaQuerySingle for Without<A> {}
+
+/// Returns true if `report` reports any error.
+fn fails(report: impl FnOnce(&mut dyn FnMut(MetaError))) -> bool {
+    let mut failed = false;
+    report(&mut |_| failed = true);
+    failed
+}
+
+/// A [`MetaQuery`] that tries `A`, then `B`, yields the first branch that matches.
+///
+/// Branches are alternatives, so they may alias each other.
+///
+/// ```
+/// match tuple.query_ref::<Or<&SingleTarget, &AreaTarget>>() {
+///     Some(Or::Left(single)) => ...,
+///     Some(Or::Right(area)) => ...,
+///     None => ...,
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Or<A, B> {
+    Left(A),
+    Right(B),
+}
+
+unsafe impl<A: MetaQuery, B: MetaQuery> MetaQuery for Or<A, B> {
+    type Output<'t> = Or<A::Output<'t>, B::Output<'t>>;
+
+    fn query_ref<'t, T: MetaTuple + ?Sized + 't>(input: &'t T) -> Option<Self::Output<'t>> {
+        A::query_ref(input)
+            .map(Or::Left)
+            .or_else(|| B::query_ref(input).map(Or::Right))
+    }
+
+    fn query_dyn_ref<'t>(input: &'t dyn MetaAny) -> Option<Self::Output<'t>> {
+        A::query_dyn_ref(input)
+            .map(Or::Left)
+            .or_else(|| B::query_dyn_ref(input).map(Or::Right))
+    }
+
+    type OutputPtr<'t> = Or<A::OutputPtr<'t>, B::OutputPtr<'t>>;
+
+    unsafe fn from_ptr<'t>(ptr: Self::OutputPtr<'t>) -> Self::Output<'t> {
+        match ptr {
+            Or::Left(ptr) => Or::Left(unsafe { A::from_ptr(ptr) }),
+            Or::Right(ptr) => Or::Right(unsafe { B::from_ptr(ptr) }),
+        }
+    }
+
+    unsafe fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(
+        input: *mut T,
+    ) -> Option<Self::OutputPtr<'t>> {
+        unsafe { A::query_mut_ptr(input) }
+            .map(Or::Left)
+            .or_else(|| unsafe { B::query_mut_ptr(input) }.map(Or::Right))
+    }
+
+    unsafe fn query_dyn_mut_ptr<'t>(input: *mut (dyn MetaAny + 't)) -> Option<Self::OutputPtr<'t>> {
+        unsafe { A::query_dyn_mut_ptr(input) }
+            .map(Or::Left)
+            .or_else(|| unsafe { B::query_dyn_mut_ptr(input) }.map(Or::Right))
+    }
+
+    fn validate() -> bool {
+        A::validate() && B::validate()
+    }
+
+    fn access(visit: &mut dyn FnMut(Access)) {
+        A::access(visit);
+        B::access(visit);
+    }
+
+    fn aliased() -> Option<MetaError> {
+        A::aliased().or_else(B::aliased)
+    }
+
+    fn report<T: MetaTuple + ?Sized>(input: &T, mutable: bool, report: &mut dyn FnMut(MetaError)) {
+        if fails(|f| A::report(input, mutable, f)) && fails(|f| B::report(input, mutable, f)) {
+            A::report(input, mutable, report);
+            B::report(input, mutable, report);
+        }
+    }
+
+    fn report_dyn(input: &dyn MetaAny, mutable: bool, report: &mut dyn FnMut(MetaError)) {
+        if fails(|f| A::report_dyn(input, mutable, f))
+            && fails(|f| B::report_dyn(input, mutable, f))
+        {
+            A::report_dyn(input, mutable, report);
+            B::report_dyn(input, mutable, report);
+        }
+    }
+}
+
+/// Storage of [`AnyOf`].
+#[doc(hidden)]
+pub trait AnyOfQuery {
+    type Options;
+
+    fn index(options: &Self::Options) -> Option<usize>;
+}
+
+/// A [`MetaQuery`] that tries each query of a tuple in order, yields the first that matches.
+///
+/// The output stores an `Option` for each query, only the matched one is `Some`.
+/// Branches are alternatives, so they may alias each other.
+///
+/// ```
+/// let AnyOf((single, area, line)) = tuple.query_ref::<AnyOf<(&Single, &Area, &Line)>>().unwrap();
+/// ```
+pub struct AnyOf<Q: AnyOfQuery>(pub Q::Options);
+
+impl<Q: AnyOfQuery> AnyOf<Q> {
+    /// Index of the query that matched.
+    pub fn index(&self) -> Option<usize> {
+        Q::index(&self.0)
+    }
+}
+
+impl<Q: AnyOfQuery<Options: Debug>> Debug for AnyOf<Q> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AnyOf").field(&self.0).finish()
+    }
+}
+
+impl<Q: AnyOfQuery<Options: PartialEq>> PartialEq for AnyOf<Q> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+macro_rules! impl_any_of {
+    ($($idx: tt $T: ident)*) => {
+        impl<$($T),*> AnyOfQuery for ($($T,)*) {
+            type Options = ($(Option<$T>,)*);
+
+            fn index(options: &Self::Options) -> Option<usize> {
+                $(if options.$idx.is_some() {
+                    return Some($idx);
+                })*
+                None
+            }
+        }
+
+        #[allow(non_snake_case)]
+        unsafe impl<$($T: MetaQuery),*> MetaQuery for AnyOf<($($T,)*)> {
+            type Output<'t> = AnyOf<($($T::Output<'t>,)*)>;
+
+            fn query_ref<'t, T: MetaTuple + ?Sized + 't>(input: &'t T) -> Option<Self::Output<'t>> {
+                let mut options = ($(None::<$T::Output<'t>>,)*);
+                $(if let Some(output) = $T::query_ref(input) {
+                    options.$idx = Some(output);
+                    return Some(AnyOf(options));
+                })*
+                None
+            }
+
+            fn query_dyn_ref<'t>(input: &'t dyn MetaAny) -> Option<Self::Output<'t>> {
+                let mut options = ($(None::<$T::Output<'t>>,)*);
+                $(if let Some(output) = $T::query_dyn_ref(input) {
+                    options.$idx = Some(output);
+                    return Some(AnyOf(options));
+                })*
+                None
+            }
+
+            type OutputPtr<'t> = ($(Option<$T::OutputPtr<'t>>,)*);
+
+            unsafe fn from_ptr<'t>(ptr: Self::OutputPtr<'t>) -> Self::Output<'t> {
+                let ($($T,)*) = ptr;
+                AnyOf(($($T.map(|ptr| unsafe { $T::from_ptr(ptr) }),)*))
+            }
+
+            unsafe fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(input: *mut T) -> Option<Self::OutputPtr<'t>> {
+                let mut options = ($(None::<$T::OutputPtr<'t>>,)*);
+                $(if let Some(output) = unsafe { $T::query_mut_ptr(input) } {
+                    options.$idx = Some(output);
+                    return Some(options);
+                })*
+                None
+            }
+
+            unsafe fn query_dyn_mut_ptr<'t>(input: *mut (dyn MetaAny + 't)) -> Option<Self::OutputPtr<'t>> {
+                let mut options = ($(None::<$T::OutputPtr<'t>>,)*);
+                $(if let Some(output) = unsafe { $T::query_dyn_mut_ptr(input) } {
+                    options.$idx = Some(output);
+                    return Some(options);
+                })*
+                None
+            }
+
+            fn validate() -> bool {
+                $($T::validate())&&*
+            }
+
+            fn access(visit: &mut dyn FnMut(Access)) {
+                $($T::access(visit);)*
+            }
+
+            fn aliased() -> Option<MetaError> {
+                None $(.or_else($T::aliased))*
+            }
+
+            fn report<T: MetaTuple + ?Sized>(input: &T, mutable: bool, report: &mut dyn FnMut(MetaError)) {
+                if $(fails(|f| $T::report(input, mutable, f)))&&* {
+                    $($T::report(input, mutable, report);)*
+                }
+            }
+
+            fn report_dyn(input: &dyn MetaAny, mutable: bool, report: &mut dyn FnMut(MetaError)) {
+                if $(fails(|f| $T::report_dyn(input, mutable, f)))&&* {
+                    $($T::report_dyn(input, mutable, report);)*
+                }
+            }
+        }
+    };
+}
+
+impl_any_of!(0 T0);
+impl_any_of!(0 T0 1 T1);
+impl_any_of!(0 T0 1 T1 2 T2);
+impl_any_of!(0 T0 1 T1 2 T2 3 T3);
+impl_any_of!(0 T0 1 T1 2 T2 3 T3 4 T4);
+impl_any_of!(0 T0 1 T1 2 T2 3 T3 4 T4 5 T5);
+impl_any_of!(0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6);
+impl_any_of!(0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7);
+impl_any_of!(0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8);
+impl_any_of!(0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9);
+impl_any_of!(0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10);
+impl_any_of!(0 T0 1 T1 2 T2 3 T3 4 T4 5 T5 6 T6 7 T7 8 T8 9 T9 10 T10 11 T11);
+
+macro_rules! aliased {
+    () => { None };
     ($A: ident $($T: ident)*) => {
-        $($A::compatible::<$T>() && )* true && validate!($($T)*)
+        $A::aliased()
+            $(.or_else(conflict::<$A, $T>))*
+            .or_else(|| aliased!($($T)*))
     };
 }
 
 macro_rules! impl_meta_query {
     ($($T: ident)*) => {
         #[allow(unused_variables, non_snake_case, clippy::unused_unit)]
-        unsafe impl<$($T: MetaQuerySingle + 'static),*> MetaQuery for ($($T,)*) {
+        unsafe impl<$($T: MetaQuery),*> MetaQuery for ($($T,)*) {
             type Output<'t> = ($($T::Output<'t>,)*);
 
             fn query_ref<'t, T: MetaTuple + ?Sized + 't>(input: &'t T) -> Option<Self::Output<'t>> {
@@ -252,16 +1271,32 @@ macro_rules! impl_meta_query {
                 ($(unsafe {$T::from_ptr($T)},)*)
             }
 
-            fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(input: &'t T) -> Option<Self::OutputPtr<'t>> {
-                Some(($($T::query_mut_ptr(input)?,)*))
+            unsafe fn query_mut_ptr<'t, T: MetaTuple + ?Sized + 't>(input: *mut T) -> Option<Self::OutputPtr<'t>> {
+                Some(($(unsafe { $T::query_mut_ptr(input) }?,)*))
             }
 
-            fn query_dyn_mut_ptr<'t>(input: &'t dyn MetaAny) -> Option<Self::OutputPtr<'t>> {
-                Some(($($T::query_dyn_mut_ptr(input)?,)*))
+            unsafe fn query_dyn_mut_ptr<'t>(input: *mut (dyn MetaAny + 't)) -> Option<Self::OutputPtr<'t>> {
+                Some(($(unsafe { $T::query_dyn_mut_ptr(input) }?,)*))
             }
 
             fn validate() -> bool {
-                validate!($($T)*)
+                Self::aliased().is_none()
+            }
+
+            fn access(visit: &mut dyn FnMut(Access)) {
+                $($T::access(visit);)*
+            }
+
+            fn aliased() -> Option<MetaError> {
+                aliased!($($T)*)
+            }
+
+            fn report<T: MetaTuple + ?Sized>(input: &T, mutable: bool, report: &mut dyn FnMut(MetaError)) {
+                $($T::report(input, mutable, report);)*
+            }
+
+            fn report_dyn(input: &dyn MetaAny, mutable: bool, report: &mut dyn FnMut(MetaError)) {
+                $($T::report_dyn(input, mutable, report);)*
             }
         }
     };
@@ -0,0 +1,252 @@
+use crate::{MetaAny, MetaSink, MetaVec, vec::FromMetaVec};
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec, vec::Vec};
+use core::{
+    any::{Any, TypeId, type_name},
+    fmt,
+};
+use serde::{
+    Deserializer, Serialize, Serializer,
+    de::{DeserializeOwned, DeserializeSeed, Error as _, IgnoredAny, MapAccess, Visitor},
+    ser::{Error as _, SerializeMap},
+};
+
+/// Behavior when encountering an item not registered in a [`MetaRegistry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Unregistered {
+    /// Silently skip the item.
+    Skip,
+    /// Return an error.
+    #[default]
+    Error,
+}
+
+type DeserializeFn =
+    fn(&mut dyn erased_serde::Deserializer) -> Result<Box<dyn Any>, erased_serde::Error>;
+
+struct Registration {
+    name: &'static str,
+    type_id: TypeId,
+    type_name: &'static str,
+    serialize: fn(&dyn Any) -> &dyn erased_serde::Serialize,
+    deserialize: DeserializeFn,
+}
+
+fn serialize_item<T: Serialize + 'static>(item: &dyn Any) -> &dyn erased_serde::Serialize {
+    item.downcast_ref::<T>()
+        .expect("Registration is keyed by `TypeId`.")
+}
+
+fn deserialize_item<T: DeserializeOwned + 'static>(
+    deserializer: &mut dyn erased_serde::Deserializer,
+) -> Result<Box<dyn Any>, erased_serde::Error> {
+    Ok(Box::new(erased_serde::deserialize::<T>(deserializer)?))
+}
+
+/// A registry of serializable types, used to serialize a [`MetaTuple`](crate::MetaTuple)
+/// as a map keyed by stable type names.
+///
+/// ```
+/// let mut registry = MetaRegistry::new();
+/// registry.register::<Attacker>("game::Attacker");
+/// registry.register::<Defender>("game::Defender");
+///
+/// let tuple = meta_tuple!(Attacker::new(), Defender::new());
+/// let json = serde_json::to_string(&registry.serialize(&tuple))?;
+///
+/// let vec: MetaVec = registry.deserialize(&mut serde_json::Deserializer::from_str(&json))?;
+/// let tuple: meta_tuple_type!(Attacker, Defender) =
+///     registry.deserialize_static(&mut serde_json::Deserializer::from_str(&json))?;
+/// ```
+#[derive(Default)]
+pub struct MetaRegistry {
+    registrations: Vec<Registration>,
+    indices: BTreeMap<TypeId, usize>,
+    names: BTreeMap<&'static str, usize>,
+    policy: Unregistered,
+}
+
+impl fmt::Debug for MetaRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetaRegistry")
+            .field("names", &self.names.keys())
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+impl MetaRegistry {
+    /// Create an empty [`MetaRegistry`].
+    pub fn new() -> Self {
+        MetaRegistry::default()
+    }
+
+    /// Returns the [`Unregistered`] policy.
+    pub fn policy(&self) -> Unregistered {
+        self.policy
+    }
+
+    /// Set the [`Unregistered`] policy, the default is [`Unregistered::Error`].
+    pub fn set_policy(&mut self, policy: Unregistered) -> &mut Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Register type `T` with a stable name, replaces the previous registration of `T`.
+    ///
+    /// # Panics
+    ///
+    /// If the name is already registered for a different type.
+    pub fn register<T: Serialize + DeserializeOwned + 'static>(
+        &mut self,
+        name: &'static str,
+    ) -> &mut Self {
+        let registration = Registration {
+            name,
+            type_id: TypeId::of::<T>(),
+            type_name: type_name::<T>(),
+            serialize: serialize_item::<T>,
+            deserialize: deserialize_item::<T>,
+        };
+        if let Some(idx) = self.names.get(name)
+            && self.registrations[*idx].type_id != registration.type_id
+        {
+            panic!(
+                "{name} is already registered for {}.",
+                self.registrations[*idx].type_name
+            );
+        }
+        match self.indices.get(&TypeId::of::<T>()) {
+            Some(idx) => {
+                self.names.remove(self.registrations[*idx].name);
+                self.names.insert(name, *idx);
+                self.registrations[*idx] = registration;
+            }
+            None => {
+                self.indices
+                    .insert(TypeId::of::<T>(), self.registrations.len());
+                self.names.insert(name, self.registrations.len());
+                self.registrations.push(registration);
+            }
+        }
+        self
+    }
+
+    /// Returns the registered name of type `T`, if exists.
+    pub fn name_of<T: 'static>(&self) -> Option<&'static str> {
+        self.indices
+            .get(&TypeId::of::<T>())
+            .map(|idx| self.registrations[*idx].name)
+    }
+
+    /// Create a [`Serialize`] implementor that serializes items in lookup order
+    /// as a map keyed by registered names.
+    ///
+    /// Only the first item of each type is serialized, matching [`MetaTuple::get`](crate::MetaTuple::get).
+    pub fn serialize<'t>(&'t self, tuple: &'t dyn MetaAny) -> SerializeMeta<'t> {
+        SerializeMeta {
+            registry: self,
+            tuple,
+        }
+    }
+
+    /// Deserialize a map keyed by registered names into a [`MetaVec`].
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        &self,
+        deserializer: D,
+    ) -> Result<MetaVec, D::Error> {
+        deserializer.deserialize_map(MetaVecVisitor(self))
+    }
+
+    /// Deserialize a map keyed by registered names into a statically typed [`MetaTuple`](crate::MetaTuple),
+    /// like `meta_tuple_type!(A, B, C)`.
+    ///
+    /// Extra items are discarded.
+    pub fn deserialize_static<'de, M: FromMetaVec, D: Deserializer<'de>>(
+        &self,
+        deserializer: D,
+    ) -> Result<M, D::Error> {
+        let mut vec = self.deserialize(deserializer)?;
+        vec.take_tuple()
+            .map_err(|name| D::Error::custom(format_args!("missing item {name}")))
+    }
+}
+
+/// Serializes a [`MetaTuple`](crate::MetaTuple) as a map keyed by registered names,
+/// see [`MetaRegistry::serialize`].
+pub struct SerializeMeta<'t> {
+    registry: &'t MetaRegistry,
+    tuple: &'t dyn MetaAny,
+}
+
+impl Serialize for SerializeMeta<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        let mut result = Ok(());
+        let mut serialized = vec![false; self.registry.registrations.len()];
+        self.tuple.visit(&mut |item, type_name| {
+            if result.is_err() {
+                return;
+            }
+            match self.registry.indices.get(&item.type_id()) {
+                Some(idx) if serialized[*idx] => (),
+                Some(idx) => {
+                    serialized[*idx] = true;
+                    let registration = &self.registry.registrations[*idx];
+                    result = map.serialize_entry(registration.name, (registration.serialize)(item));
+                }
+                None => {
+                    if self.registry.policy == Unregistered::Error {
+                        result = Err(S::Error::custom(format_args!(
+                            "{type_name} is not registered"
+                        )));
+                    }
+                }
+            }
+        });
+        result?;
+        map.end()
+    }
+}
+
+struct MetaVecVisitor<'t>(&'t MetaRegistry);
+
+impl<'de> Visitor<'de> for MetaVecVisitor<'_> {
+    type Value = MetaVec;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map keyed by registered type names")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut vec = MetaVec::with_capacity(map.size_hint().unwrap_or(0));
+        while let Some(name) = map.next_key::<String>()? {
+            match self.0.names.get(name.as_str()) {
+                Some(idx) => {
+                    let registration = &self.0.registrations[*idx];
+                    let item = map.next_value_seed(RegistrationSeed(registration))?;
+                    vec.push_boxed(item, registration.type_name);
+                }
+                None => match self.0.policy {
+                    Unregistered::Skip => {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                    Unregistered::Error => {
+                        return Err(A::Error::custom(format_args!("{name} is not registered")));
+                    }
+                },
+            }
+        }
+        Ok(vec)
+    }
+}
+
+struct RegistrationSeed<'t>(&'t Registration);
+
+impl<'de> DeserializeSeed<'de> for RegistrationSeed<'_> {
+    type Value = Box<dyn Any>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let mut erased = <dyn erased_serde::Deserializer>::erase(deserializer);
+        (self.0.deserialize)(&mut erased).map_err(D::Error::custom)
+    }
+}
@@ -0,0 +1,241 @@
+use crate::{
+    Access, ErasedBundlePtr, ErasedInner, ErasedInnerMut, ErasedInnerPtr, MetaAny, MetaBundle,
+    MetaQuery, MetaSink, MetaTuple,
+};
+use alloc::vec::Vec;
+use core::{
+    any::{Any, TypeId, type_name},
+    marker::PhantomData,
+    ptr,
+};
+
+/// Items of a [`MetaTuple`] not accessed by a [`MetaQuery`], see [`MetaTuple::split_query_mut`].
+///
+/// Items the query accesses mutably are hidden, items it reads can still be read
+/// but not mutated, unless hidden by [`Remainder::hide_reads`].
+/// Items that cannot be mutated in the original [`MetaTuple`], like items joined by reference,
+/// can only be read.
+///
+/// Items are found when splitting and borrowed, so they cannot be moved into a [`MetaSink`].
+pub struct Remainder<'t> {
+    items: Items,
+    mutable: Items,
+    access: fn(&mut dyn FnMut(Access)),
+    p: PhantomData<&'t mut ()>,
+}
+
+/// Pointers to items with their [`TypeId`] and type name, in lookup order.
+///
+/// Only valid for writes in [`Remainder::mutable`], never erased mutably otherwise.
+struct Items(Vec<(*mut dyn Any, TypeId, &'static str)>);
+
+impl<'t> Remainder<'t> {
+    /// Split `input` into the output of `Q` and a [`Remainder`].
+    ///
+    /// # Panics
+    ///
+    /// If fields alias, like `(&mut A, &A)`.
+    pub(crate) fn split<Q: MetaQuery, T: MetaTuple + ?Sized + 't>(
+        input: &'t mut T,
+    ) -> Option<(Q::Output<'t>, Self)> {
+        if !Q::validate() {
+            panic!("{} is not a valid MetaQuery.", type_name::<Q>());
+        }
+        // Safety:
+        //
+        // Safe since `input` is borrowed mutably for `'t`, `validate` ensures no aliasing
+        // and the remainder hides items accessed by `Q`.
+        unsafe {
+            let this: *mut T = input;
+            let remainder = Remainder::new::<Q>(this);
+            let output = Q::query_mut_ptr(this)?;
+            Some((Q::from_ptr(output), remainder))
+        }
+    }
+
+    /// Split `input` into the output of `Q` and a [`Remainder`].
+    ///
+    /// # Panics
+    ///
+    /// If fields alias, like `(&mut A, &A)`.
+    #[doc(hidden)]
+    pub fn split_dyn<Q: MetaQuery>(input: &'t mut dyn MetaAny) -> Option<(Q::Output<'t>, Self)> {
+        if !Q::validate() {
+            panic!("{} is not a valid MetaQuery.", type_name::<Q>());
+        }
+        // Safety:
+        //
+        // Safe since `input` is borrowed mutably for `'t`, `validate` ensures no aliasing
+        // and the remainder hides items accessed by `Q`.
+        unsafe {
+            let this: *mut dyn MetaAny = input;
+            let remainder = Remainder::new::<Q>(this);
+            let output = Q::query_dyn_mut_ptr(this)?;
+            Some((Q::from_ptr(output), remainder))
+        }
+    }
+
+    /// Find every item not accessed mutably by `Q`, before any reference to the output of `Q` exists.
+    ///
+    /// # Safety
+    ///
+    /// `this` must be valid for writes for `'t`.
+    unsafe fn new<Q: MetaQuery>(this: *mut (impl MetaAny + ?Sized)) -> Self {
+        let mut mutable = Vec::new();
+        unsafe { &mut *this }.visit_mut(&mut |item, type_name| {
+            let type_id = (*item).type_id();
+            mutable.push((ptr::from_mut(item), type_id, type_name))
+        });
+        // `visit_mut` visits items in the same order as `visit`, but skips immutable ones.
+        let mut candidates = mutable.into_iter().peekable();
+        let mut items = Vec::new();
+        let mut mutable = Vec::new();
+        unsafe { &*this }.visit(&mut |item, type_name| {
+            let type_id = (*item).type_id();
+            let found = candidates.next_if(|(candidate, candidate_id, _)| {
+                *candidate_id == type_id && ptr::addr_eq(*candidate, item)
+            });
+            let accessed = accessed(Q::access, type_id);
+            if accessed == Some(true) {
+                return;
+            }
+            match found {
+                Some(item) if accessed.is_none() => {
+                    items.push(item);
+                    mutable.push(item);
+                }
+                _ => items.push((ptr::from_ref(item).cast_mut(), type_id, type_name)),
+            }
+        });
+        Remainder {
+            items: Items(items),
+            mutable: Items(mutable),
+            access: Q::access,
+            p: PhantomData,
+        }
+    }
+
+    /// Hide items read by the query as well.
+    pub fn hide_reads(mut self) -> Self {
+        let access = self.access;
+        self.items
+            .0
+            .retain(|(_, type_id, _)| accessed(access, *type_id).is_none());
+        self
+    }
+}
+
+/// Returns `Some(mutable)` if the query accesses items of `type_id`.
+fn accessed(access: fn(&mut dyn FnMut(Access)), type_id: TypeId) -> Option<bool> {
+    let mut result = None;
+    access(&mut |item| {
+        if item.type_id == type_id {
+            result = Some(result.unwrap_or(false) || item.mutable)
+        }
+    });
+    result
+}
+
+impl MetaBundle for Items {
+    fn get_field(&self, idx: usize) -> Option<&dyn Any> {
+        // Safety:
+        //
+        // Safe since items are valid for reads while the remainder is borrowed.
+        self.0.get(idx).map(|(item, _, _)| unsafe { &**item })
+    }
+
+    fn get_field_mut(&mut self, idx: usize) -> Option<&mut dyn Any> {
+        // Safety:
+        //
+        // Safe since only `Remainder::mutable` is borrowed mutably.
+        self.0.get(idx).map(|(item, _, _)| unsafe { &mut **item })
+    }
+
+    fn field_count(&self) -> usize {
+        self.0.len()
+    }
+
+    fn field_type_name(&self, idx: usize) -> Option<&'static str> {
+        self.0.get(idx).map(|(_, _, type_name)| *type_name)
+    }
+
+    fn field_type_id(&self, idx: usize) -> Option<TypeId> {
+        self.0.get(idx).map(|(_, type_id, _)| *type_id)
+    }
+
+    unsafe fn get_field_ptr(this: *mut Self, idx: usize) -> Option<(*mut (), TypeId)> {
+        let (item, type_id, _) = unsafe { &(*this).0 }.get(idx)?;
+        Some((item.cast(), *type_id))
+    }
+}
+
+unsafe impl MetaAny for Remainder<'_> {
+    fn as_erased(&self) -> ErasedInner<'_> {
+        ErasedInner::Struct(&self.items)
+    }
+
+    fn as_erased_mut(&mut self) -> ErasedInnerMut<'_> {
+        ErasedInnerMut::Struct(&mut self.mutable)
+    }
+
+    unsafe fn as_erased_ptr(&self, this: *mut ()) -> ErasedInnerPtr<'_> {
+        unsafe { Self::erase_ptr(this.cast()) }
+    }
+
+    unsafe fn erase_ptr<'t>(this: *mut Self) -> ErasedInnerPtr<'t>
+    where
+        Self: 't,
+    {
+        ErasedInnerPtr::Struct(unsafe { ErasedBundlePtr::new(&raw mut (*this).mutable) })
+    }
+
+    fn visit(&self, f: &mut dyn FnMut(&dyn Any, &'static str)) {
+        ErasedInner::Struct(&self.items).visit(f)
+    }
+
+    fn visit_mut(&mut self, f: &mut dyn FnMut(&mut dyn Any, &'static str)) {
+        ErasedInnerMut::Struct(&mut self.mutable).visit_mut(f)
+    }
+
+    crate::__impl_into_sink_boxed!();
+}
+
+unsafe impl MetaTuple for Remainder<'_> {
+    fn get<T: 'static>(&self) -> Option<&T> {
+        self.get_all().next()
+    }
+
+    fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.get_all_mut().next()
+    }
+
+    unsafe fn get_mut_ptr<T: 'static>(this: *mut Self) -> Option<*mut T> {
+        unsafe { &(*this).mutable.0 }
+            .iter()
+            .find_map(|(item, type_id, _)| (*type_id == TypeId::of::<T>()).then(|| item.cast()))
+    }
+
+    fn get_all<T: 'static>(&self) -> impl Iterator<Item = &T> {
+        // Safety:
+        //
+        // Safe since items are valid for reads while the remainder is borrowed.
+        self.items
+            .0
+            .iter()
+            .filter(|(_, type_id, _)| *type_id == TypeId::of::<T>())
+            .map(|&(item, _, _)| unsafe { &*item.cast::<T>() })
+    }
+
+    fn get_all_mut<T: 'static>(&mut self) -> impl Iterator<Item = &mut T> {
+        // Safety:
+        //
+        // Safe since mutable items are disjoint and valid for writes while the remainder is borrowed.
+        self.mutable
+            .0
+            .iter()
+            .filter(|(_, type_id, _)| *type_id == TypeId::of::<T>())
+            .map(|&(item, _, _)| unsafe { &mut *item.cast::<T>() })
+    }
+
+    fn into_sink(self, _: &mut impl MetaSink) {}
+}
@@ -0,0 +1,215 @@
+use crate::{Join, MetaItem, MetaTuple};
+use core::marker::PhantomData;
+
+/// Index of an item that is the [`MetaTuple`] itself, i.e. a [`MetaItem`](struct@crate::MetaItem).
+pub struct Here;
+
+/// Index of an item in the left side of a [`Join`].
+pub struct Left<I>(PhantomData<I>);
+
+/// Index of an item in the right side of a [`Join`].
+pub struct Right<I>(PhantomData<I>);
+
+/// Index of an item in the `N`th field of a struct deriving `MetaTuple`.
+pub struct Field<const N: usize>;
+
+/// A [`MetaTuple`] that is statically known to contain an item of type `T`.
+///
+/// `I` is the type level index of the item, it is inferred by the compiler
+/// and should be left as a generic parameter in bounds,
+/// i.e. `M: Select<Attacker, I>` instead of `M: Select<Attacker>`.
+///
+/// # Semantics
+///
+/// If a type is duplicated, the index cannot be inferred and must be specified manually.
+/// This might return a different item from [`MetaTuple::get`].
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` does not contain an item of type `{T}`",
+    label = "missing `{T}`"
+)]
+pub trait Select<T: 'static, I>: MetaTuple {
+    /// Obtain the item.
+    fn select(&self) -> &T;
+}
+
+/// A [`MetaTuple`] that is statically known to contain a mutable item of type `T`.
+///
+/// Items joined by reference do not implement this trait.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` does not contain a mutable item of type `{T}`",
+    label = "missing mutable `{T}`"
+)]
+pub trait SelectMut<T: 'static, I>: Select<T, I> {
+    /// Obtain the item mutably.
+    fn select_mut(&mut self) -> &mut T;
+}
+
+impl<T: 'static> Select<T, Here> for MetaItem<T> {
+    fn select(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: 'static> SelectMut<T, Here> for MetaItem<T> {
+    fn select_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: 'static, I, M: Select<T, I> + ?Sized> Select<T, I> for &M {
+    fn select(&self) -> &T {
+        (**self).select()
+    }
+}
+
+impl<T: 'static, I, M: Select<T, I>> Select<T, I> for &mut M {
+    fn select(&self) -> &T {
+        (**self).select()
+    }
+}
+
+impl<T: 'static, I, M: SelectMut<T, I>> SelectMut<T, I> for &mut M {
+    fn select_mut(&mut self) -> &mut T {
+        (**self).select_mut()
+    }
+}
+
+impl<T: 'static, I, A: Select<T, I>, B: MetaTuple> Select<T, Left<I>> for Join<A, B> {
+    fn select(&self) -> &T {
+        self.0.select()
+    }
+}
+
+impl<T: 'static, I, A: SelectMut<T, I>, B: MetaTuple> SelectMut<T, Left<I>> for Join<A, B> {
+    fn select_mut(&mut self) -> &mut T {
+        self.0.select_mut()
+    }
+}
+
+impl<T: 'static, I, A: MetaTuple, B: Select<T, I>> Select<T, Right<I>> for Join<A, B> {
+    fn select(&self) -> &T {
+        self.1.select()
+    }
+}
+
+impl<T: 'static, I, A: MetaTuple, B: SelectMut<T, I>> SelectMut<T, Right<I>> for Join<A, B> {
+    fn select_mut(&mut self) -> &mut T {
+        self.1.select_mut()
+    }
+}
+
+/// Infallible lookups of items that are statically known to exist.
+///
+/// The index `I` is a parameter of the trait instead of the methods,
+/// so it is inferred in `get_static::<T>()`.
+pub trait GetStatic<I> {
+    /// Obtain an item that is statically known to exist, see [`Select`].
+    fn get_static<T: 'static>(&self) -> &T
+    where
+        Self: Select<T, I>,
+    {
+        self.select()
+    }
+
+    /// Obtain a mutable item that is statically known to exist, see [`SelectMut`].
+    fn get_static_mut<T: 'static>(&mut self) -> &mut T
+    where
+        Self: SelectMut<T, I>,
+    {
+        self.select_mut()
+    }
+}
+
+impl<M: ?Sized, I> GetStatic<I> for M {}
+
+/// A [`MetaTuple`] that an item of type `T` can be moved out of.
+///
+/// Like [`Select`], `I` is the type level index of the item and should be inferred.
+#[diagnostic::on_unimplemented(
+    message = "cannot move an item of type `{T}` out of `{Self}`",
+    label = "missing owned `{T}`"
+)]
+pub trait Take<T: 'static, I>: Select<T, I> + Sized {
+    /// The remaining [`MetaTuple`] after the item is removed.
+    type Rest: MetaTuple;
+
+    /// Move the item out, returns the item and the remaining [`MetaTuple`].
+    fn take_item(self) -> (T, Self::Rest);
+}
+
+impl<T: 'static> Take<T, Here> for MetaItem<T> {
+    type Rest = ();
+
+    fn take_item(self) -> (T, Self::Rest) {
+        (self.0, ())
+    }
+}
+
+impl<T: 'static, I, A: Take<T, I>, B: MetaTuple> Take<T, Left<I>> for Join<A, B> {
+    type Rest = Join<A::Rest, B>;
+
+    fn take_item(self) -> (T, Self::Rest) {
+        let (item, rest) = self.0.take_item();
+        (item, Join(rest, self.1))
+    }
+}
+
+impl<T: 'static, I, A: MetaTuple, B: Take<T, I>> Take<T, Right<I>> for Join<A, B> {
+    type Rest = Join<A, B::Rest>;
+
+    fn take_item(self) -> (T, Self::Rest) {
+        let (item, rest) = self.1.take_item();
+        (item, Join(self.0, rest))
+    }
+}
+
+/// A [`MetaTuple`] that multiple items can be moved out of, where `Q` is a tuple like `(A, B, C)`.
+///
+/// `I` is a tuple of type level indices and should be inferred.
+pub trait SplitQuery<Q, I>: Sized {
+    /// The remaining [`MetaTuple`] after the items are removed.
+    type Rest: MetaTuple;
+
+    /// Move the items out, returns the items and the remaining [`MetaTuple`].
+    fn split_items(self) -> (Q, Self::Rest);
+}
+
+impl<M: MetaTuple> SplitQuery<(), ()> for M {
+    type Rest = M;
+
+    fn split_items(self) -> ((), Self::Rest) {
+        ((), self)
+    }
+}
+
+macro_rules! impl_split_query {
+    ($A: ident $IA: ident $($T: ident $I: ident)*) => {
+        #[allow(non_snake_case)]
+        impl<M, $A: 'static, $IA, $($T: 'static, $I,)*> SplitQuery<($A, $($T,)*), ($IA, $($I,)*)> for M
+        where
+            M: Take<$A, $IA>,
+            M::Rest: SplitQuery<($($T,)*), ($($I,)*)>,
+        {
+            type Rest = <M::Rest as SplitQuery<($($T,)*), ($($I,)*)>>::Rest;
+
+            fn split_items(self) -> (($A, $($T,)*), Self::Rest) {
+                let ($A, rest) = self.take_item();
+                let (($($T,)*), rest) = rest.split_items();
+                (($A, $($T,)*), rest)
+            }
+        }
+    };
+}
+
+impl_split_query!(A IA);
+impl_split_query!(A IA B IB);
+impl_split_query!(A IA B IB C IC);
+impl_split_query!(A IA B IB C IC D ID);
+impl_split_query!(A IA B IB C IC D ID E IE);
+impl_split_query!(A IA B IB C IC D ID E IE F IF);
+impl_split_query!(A IA B IB C IC D ID E IE F IF G IG);
+impl_split_query!(A IA B IB C IC D ID E IE F IF G IG H IH);
+impl_split_query!(A IA B IB C IC D ID E IE F IF G IG H IH J IJ);
+impl_split_query!(A IA B IB C IC D ID E IE F IF G IG H IH J IJ K IK);
+impl_split_query!(A IA B IB C IC D ID E IE F IF G IG H IH J IJ K IK L IL);
+impl_split_query!(A IA B IB C IC D ID E IE F IF G IG H IH J IJ K IK L IL N IN);
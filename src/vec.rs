@@ -0,0 +1,228 @@
+use crate::{
+    ErasedBundlePtr, ErasedInner, ErasedInnerMut, ErasedInnerPtr, IntoMetaTuple, Join, MetaAny,
+    MetaBundle, MetaItem, MetaSink, MetaTuple,
+};
+use alloc::{boxed::Box, vec::Vec};
+use core::any::{Any, TypeId, type_name};
+
+/// A heap allocated [`MetaTuple`] that can be modified at runtime.
+///
+/// Items are stored in insertion order and use the same semantics as [`Join`](crate::Join),
+/// i.e. lookups return the first item of the correct type.
+///
+/// ```
+/// let mut vec = MetaVec::new();
+/// vec.push(1i32);
+/// vec.extend((2.0f32, "hello"));
+/// assert_eq!(vec.get::<f32>(), Some(&2.0));
+/// ```
+#[derive(Debug, Default)]
+pub struct MetaVec(Vec<(Box<dyn Any>, TypeId, &'static str)>);
+
+impl MetaVec {
+    /// Create an empty [`MetaVec`].
+    pub const fn new() -> Self {
+        MetaVec(Vec::new())
+    }
+
+    /// Create an empty [`MetaVec`] with at least the specified capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        MetaVec(Vec::with_capacity(capacity))
+    }
+
+    /// Returns the number of items.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no items.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Add an item to the end.
+    pub fn push<T: 'static>(&mut self, item: T) {
+        self.0
+            .push((Box::new(item), TypeId::of::<T>(), type_name::<T>()))
+    }
+
+    /// Remove the first item of type `T`, if exists.
+    pub fn remove<T: 'static>(&mut self) -> Option<T> {
+        let idx = self
+            .0
+            .iter()
+            .position(|(_, type_id, _)| *type_id == TypeId::of::<T>())?;
+        self.0.remove(idx).0.downcast().ok().map(|x| *x)
+    }
+
+    /// Retain only the items specified by the predicate.
+    pub fn retain(&mut self, mut f: impl FnMut(&dyn Any) -> bool) {
+        self.0.retain(|(x, _, _)| f(&**x))
+    }
+
+    /// Remove all items.
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    /// Move items out to build a statically typed [`MetaTuple`],
+    /// returns the type name of the first missing item on failure.
+    ///
+    /// ```
+    /// let mut vec = MetaVec::new();
+    /// vec.extend((1i32, "hello"));
+    /// let tuple = vec.take_tuple::<meta_tuple_type!(&str, i32)>().unwrap();
+    /// ```
+    pub fn take_tuple<M: FromMetaVec>(&mut self) -> Result<M, &'static str> {
+        M::from_meta_vec(self)
+    }
+
+    /// Move all owned items of a [`MetaTuple`] to the end.
+    ///
+    /// Items joined by reference, like `&T` or `&mut T`, cannot be moved and are skipped.
+    ///
+    /// ```
+    /// let int = 1i32;
+    /// let mut vec = MetaVec::new();
+    /// vec.extend(meta_tuple!(&int, 'c'));
+    /// assert_eq!(vec.len(), 1);
+    /// ```
+    pub fn extend(&mut self, items: impl IntoMetaTuple) {
+        items.into_meta_tuple().into_sink(self)
+    }
+}
+
+impl MetaSink for MetaVec {
+    fn push<T: 'static>(&mut self, item: T) {
+        MetaVec::push(self, item)
+    }
+
+    fn push_boxed(&mut self, item: Box<dyn Any>, type_name: &'static str) {
+        let type_id = (*item).type_id();
+        self.0.push((item, type_id, type_name))
+    }
+}
+
+impl MetaBundle for MetaVec {
+    fn get_field(&self, idx: usize) -> Option<&dyn Any> {
+        self.0.get(idx).map(|(x, _, _)| &**x)
+    }
+
+    fn get_field_mut(&mut self, idx: usize) -> Option<&mut dyn Any> {
+        self.0.get_mut(idx).map(|(x, _, _)| &mut **x)
+    }
+
+    fn field_count(&self) -> usize {
+        self.0.len()
+    }
+
+    fn field_type_name(&self, idx: usize) -> Option<&'static str> {
+        self.0.get(idx).map(|(_, _, type_name)| *type_name)
+    }
+
+    fn field_type_id(&self, idx: usize) -> Option<TypeId> {
+        self.0.get(idx).map(|(_, type_id, _)| *type_id)
+    }
+
+    unsafe fn get_field_ptr(this: *mut Self, idx: usize) -> Option<(*mut (), TypeId)> {
+        let (item, type_id, _) = unsafe { &(*this).0 }.get(idx)?;
+        Some(((&raw const **item).cast_mut().cast(), *type_id))
+    }
+}
+
+unsafe impl MetaAny for MetaVec {
+    fn as_erased(&self) -> ErasedInner<'_> {
+        ErasedInner::Struct(self)
+    }
+
+    fn as_erased_mut(&mut self) -> ErasedInnerMut<'_> {
+        ErasedInnerMut::Struct(self)
+    }
+
+    unsafe fn as_erased_ptr(&self, this: *mut ()) -> ErasedInnerPtr<'_> {
+        unsafe { Self::erase_ptr(this.cast()) }
+    }
+
+    unsafe fn erase_ptr<'t>(this: *mut Self) -> ErasedInnerPtr<'t>
+    where
+        Self: 't,
+    {
+        ErasedInnerPtr::Struct(unsafe { ErasedBundlePtr::new(this) })
+    }
+
+    fn static_type_id() -> Option<TypeId> {
+        Some(TypeId::of::<Self>())
+    }
+
+    fn meta_type_id(&self) -> Option<TypeId> {
+        Self::static_type_id()
+    }
+
+    fn visit(&self, f: &mut dyn FnMut(&dyn Any, &'static str)) {
+        for (item, _, type_name) in &self.0 {
+            f(&**item, type_name)
+        }
+    }
+
+    fn visit_mut(&mut self, f: &mut dyn FnMut(&mut dyn Any, &'static str)) {
+        for (item, _, type_name) in &mut self.0 {
+            f(&mut **item, type_name)
+        }
+    }
+
+    crate::__impl_into_sink_boxed!();
+}
+
+unsafe impl MetaTuple for MetaVec {
+    fn get<T: 'static>(&self) -> Option<&T> {
+        self.0.iter().find_map(|(x, _, _)| x.downcast_ref())
+    }
+
+    fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.0.iter_mut().find_map(|(x, _, _)| x.downcast_mut())
+    }
+
+    unsafe fn get_mut_ptr<T: 'static>(this: *mut Self) -> Option<*mut T> {
+        (0..)
+            .map_while(|idx| unsafe { Self::get_field_ptr(this, idx) })
+            .find_map(|(ptr, type_id)| (type_id == TypeId::of::<T>()).then_some(ptr.cast()))
+    }
+
+    fn get_all<T: 'static>(&self) -> impl Iterator<Item = &T> {
+        self.0.iter().filter_map(|(x, _, _)| x.downcast_ref())
+    }
+
+    fn get_all_mut<T: 'static>(&mut self) -> impl Iterator<Item = &mut T> {
+        self.0.iter_mut().filter_map(|(x, _, _)| x.downcast_mut())
+    }
+
+    fn into_sink(self, sink: &mut impl MetaSink) {
+        for (item, _, type_name) in self.0 {
+            sink.push_boxed(item, type_name)
+        }
+    }
+}
+
+/// A statically typed [`MetaTuple`] that can be built by moving items out of a [`MetaVec`].
+pub trait FromMetaVec: MetaTuple + Sized {
+    /// Move items out of a [`MetaVec`], returns the type name of the first missing item on failure.
+    fn from_meta_vec(vec: &mut MetaVec) -> Result<Self, &'static str>;
+}
+
+impl FromMetaVec for () {
+    fn from_meta_vec(_: &mut MetaVec) -> Result<Self, &'static str> {
+        Ok(())
+    }
+}
+
+impl<T: 'static> FromMetaVec for MetaItem<T> {
+    fn from_meta_vec(vec: &mut MetaVec) -> Result<Self, &'static str> {
+        vec.remove().map(MetaItem).ok_or(type_name::<T>())
+    }
+}
+
+impl<A: FromMetaVec, B: FromMetaVec> FromMetaVec for Join<A, B> {
+    fn from_meta_vec(vec: &mut MetaVec) -> Result<Self, &'static str> {
+        Ok(Join(A::from_meta_vec(vec)?, B::from_meta_vec(vec)?))
+    }
+}
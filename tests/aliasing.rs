@@ -0,0 +1,348 @@
+//! Disjoint mutable borrows, run `cargo +nightly miri test` to check for undefined behavior,
+//! with and without the `alloc` feature.
+use meta_tuple::{DynMetaTuple, MetaAny, MetaQuery, MetaTuple, meta_tuple};
+#[cfg(feature = "alloc")]
+use meta_tuple::{All, MetaMap, MetaVec, pipeline::Maybe};
+
+#[derive(Debug, MetaTuple)]
+struct Bundle {
+    int: i32,
+    float: f32,
+    string: String,
+}
+
+#[derive(Debug, MetaTuple)]
+enum Pair {
+    Two(i32, i32),
+    Mixed(i32, u8),
+    None,
+}
+
+#[derive(Debug, MetaTuple)]
+enum Tagged {
+    Named {
+        int: i32,
+        #[meta(skip)]
+        tag: u8,
+        c: char,
+    },
+}
+
+#[derive(MetaQuery)]
+struct Query<'t> {
+    int: &'t mut i32,
+    float: &'t mut f32,
+    string: &'t String,
+}
+
+fn bundle() -> Bundle {
+    Bundle {
+        int: 1,
+        float: 2.0,
+        string: String::from("a"),
+    }
+}
+
+#[test]
+pub fn test_joined() {
+    let mut float = 2.0f32;
+    let mut tuple = meta_tuple!(1i32, &mut float, String::from("a"));
+    let (int, float, string) = tuple.query_mut::<(&mut i32, &mut f32, &String)>().unwrap();
+    *int += 1;
+    *float += 1.0;
+    assert_eq!(string, "a");
+    *int += 1;
+    *float += 1.0;
+    let query = tuple.query_mut::<Query>().unwrap();
+    *query.int += 1;
+    *query.float += 1.0;
+    assert_eq!(query.string, "a");
+    *query.int += 1;
+    assert_eq!(tuple.get::<i32>(), Some(&5));
+    assert_eq!(tuple.get::<f32>(), Some(&5.0));
+}
+
+#[test]
+pub fn test_struct() {
+    let mut bundle = bundle();
+    let (int, float) = bundle.query_mut::<(&mut i32, &mut f32)>().unwrap();
+    *int += 1;
+    *float += 1.0;
+    *int += 1;
+    assert_eq!(bundle.int, 3);
+    assert_eq!(bundle.float, 3.0);
+
+    let mut tuple = meta_tuple!(#bundle, 'c', #Some(4u8));
+    let query = tuple.query_mut::<Query>().unwrap();
+    *query.int += 1;
+    assert_eq!(tuple.get::<i32>(), Some(&4));
+
+    let (int, c, byte) = tuple.query_mut::<(&mut i32, &mut char, &mut u8)>().unwrap();
+    *int += 1;
+    *c = 'd';
+    *byte += 1;
+    *int += 1;
+    assert_eq!(tuple.get::<i32>(), Some(&6));
+    assert_eq!(tuple.get::<char>(), Some(&'d'));
+    assert_eq!(tuple.get::<u8>(), Some(&5));
+}
+
+#[test]
+pub fn test_dynamic() {
+    let mut boxed: Box<dyn MetaAny> = Box::new(meta_tuple!(#bundle(), 'c'));
+    let (int, float, c) = <(&mut i32, &mut f32, &mut char)>::query_dyn_mut(&mut *boxed).unwrap();
+    *int += 1;
+    *float += 1.0;
+    *c = 'd';
+    *int += 1;
+    assert_eq!(boxed.get::<i32>(), Some(&3));
+
+    let dynamic = DynMetaTuple::from_mut(&mut *boxed);
+    let query = dynamic.query_mut::<Query>().unwrap();
+    *query.int += 1;
+    *query.float += 1.0;
+    *query.int += 1;
+    assert_eq!(dynamic.get::<i32>(), Some(&5));
+
+    let mut tuple = meta_tuple!(#dynamic, 1u8);
+    let (int, byte, c) = tuple.query_mut::<(&mut i32, &mut u8, &mut char)>().unwrap();
+    *int += 1;
+    *byte += 1;
+    *c = 'e';
+    *int += 1;
+    assert_eq!(tuple.get::<i32>(), Some(&7));
+    assert_eq!(boxed.get::<char>(), Some(&'e'));
+}
+
+fn bump<T: MetaTuple + ?Sized>(tuple: &mut T) {
+    let mut joined = meta_tuple!(#tuple, 1u8);
+    let (int, byte) = joined.query_mut::<(&mut i32, &mut u8)>().unwrap();
+    *int += 1;
+    *byte += 1;
+    *int += 1;
+    assert_eq!(joined.get::<u8>(), Some(&2));
+}
+
+#[test]
+pub fn test_unsized() {
+    let mut tuple = meta_tuple!(1i32, 'c');
+    bump(DynMetaTuple::from_mut(&mut tuple));
+    bump(&mut tuple);
+    assert_eq!(tuple.get::<i32>(), Some(&5));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn test_heap() {
+    let mut vec = MetaVec::new();
+    vec.push(1i32);
+    vec.push(2.0f32);
+    vec.push(String::from("a"));
+    let query = vec.query_mut::<Query>().unwrap();
+    *query.int += 1;
+    *query.float += 1.0;
+    *query.int += 1;
+    assert_eq!(vec.get::<i32>(), Some(&3));
+
+    let mut map = MetaMap::new();
+    map.insert(1i32);
+    map.insert(2.0f32);
+    map.insert(String::from("a"));
+    let query = map.query_mut::<Query>().unwrap();
+    *query.int += 1;
+    *query.float += 1.0;
+    *query.int += 1;
+    assert_eq!(map.get::<i32>(), Some(&3));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn test_all() {
+    let mut int = 2i32;
+    let mut tuple = meta_tuple!(1i32, &mut int, #bundle(), 2.0f32);
+    let (all, float) = tuple.query_mut::<(All<&mut i32>, &mut f32)>().unwrap();
+    let items: Vec<_> = all.collect();
+    assert_eq!(items.len(), 3);
+    for item in items {
+        *item += 1;
+        *float += 1.0;
+    }
+    assert_eq!(
+        tuple.get_all::<i32>().copied().collect::<Vec<_>>(),
+        [2, 3, 2]
+    );
+
+    let dynamic: &mut dyn MetaAny = &mut tuple;
+    let items: Vec<_> = dynamic.get_all_mut::<i32>().collect();
+    for item in items {
+        *item += 1;
+    }
+    assert_eq!(int, 4);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn test_all_nested() {
+    let mut inner = meta_tuple!(3i32, #Some(4i32));
+    let mut tuple = meta_tuple!(#Some(1i32), 2i32).join_dyn_mut(&mut inner);
+    let items: Vec<_> = tuple.get_all_mut::<i32>().collect();
+    assert_eq!(items.len(), 4);
+    for item in items {
+        *item += 1;
+    }
+    assert_eq!(
+        tuple.get_all::<i32>().copied().collect::<Vec<_>>(),
+        [2, 3, 4, 5]
+    );
+
+    let mut boxed = meta_tuple!(#Box::new(meta_tuple!(1i32, #Some(2i32))), 3i32);
+    let dynamic: &mut dyn MetaAny = &mut boxed;
+    let items: Vec<_> = dynamic.get_all_mut::<i32>().collect();
+    for item in items {
+        *item += 1;
+    }
+    assert_eq!(
+        boxed.get_all::<i32>().copied().collect::<Vec<_>>(),
+        [2, 3, 4]
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn test_option() {
+    let mut tuple =
+        meta_tuple!(#Some(1i32), #Some(Box::new(2u8)), #Maybe(Some(meta_tuple!(3u16, #Some('c')))));
+    let (int, byte, short, c) = tuple
+        .query_mut::<(&mut i32, &mut Box<u8>, &mut u16, &mut char)>()
+        .unwrap();
+    *int += 1;
+    **byte += 1;
+    *short += 1;
+    *c = 'd';
+    *int += 1;
+    **byte += 1;
+    assert_eq!(tuple.get::<i32>(), Some(&3));
+    assert_eq!(tuple.get::<char>(), Some(&'d'));
+
+    let dynamic: &mut dyn MetaAny = &mut tuple;
+    let (all, short) = dynamic.query_mut::<(All<&mut i32>, &mut u16)>().unwrap();
+    for int in all {
+        *int += 1;
+        *short += 1;
+    }
+    let items: Vec<_> = dynamic.get_all_mut::<char>().collect();
+    for item in items {
+        *item = 'e';
+    }
+    assert_eq!(dynamic.get::<u16>(), Some(&5));
+    assert_eq!(dynamic.get::<char>(), Some(&'e'));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn test_heap_all() {
+    let mut vec = MetaVec::new();
+    vec.push(1i32);
+    vec.push(2.0f32);
+    vec.push(3i32);
+    let (all, float) = vec.query_mut::<(All<&mut i32>, &mut f32)>().unwrap();
+    let items: Vec<_> = all.collect();
+    for item in items {
+        *item += 1;
+        *float += 1.0;
+    }
+    let (all,) = vec.query_mut::<(All<&mut i32>,)>().unwrap();
+    for item in all {
+        *item += 1;
+    }
+    assert_eq!(vec.get_all::<i32>().copied().collect::<Vec<_>>(), [3, 5]);
+    assert_eq!(vec.get::<f32>(), Some(&4.0));
+
+    let mut map = MetaMap::new();
+    map.insert(1i32);
+    map.insert(2.0f32);
+    let mut tuple = meta_tuple!(#&mut vec, 'c').join_dyn_mut(&mut map);
+    let (all, c) = tuple.query_mut::<(All<&mut i32>, &mut char)>().unwrap();
+    let items: Vec<_> = all.collect();
+    for item in items {
+        *item += 1;
+        *c = 'd';
+    }
+    let (float, int) = tuple.query_mut::<(&mut f32, &mut i32)>().unwrap();
+    *float += 1.0;
+    *int += 1;
+    *float += 1.0;
+    assert_eq!(vec.get_all::<i32>().copied().collect::<Vec<_>>(), [5, 6]);
+    assert_eq!(vec.get::<f32>(), Some(&6.0));
+    assert_eq!(map.get::<i32>(), Some(&2));
+}
+
+#[test]
+pub fn test_enum() {
+    let mut pair = Pair::Mixed(1, 2);
+    let (int, byte) = pair.query_mut::<(&mut i32, &mut u8)>().unwrap();
+    *int += 1;
+    *byte += 1;
+    *int += 1;
+    assert_eq!(pair.get::<i32>(), Some(&3));
+    assert_eq!(pair.get::<u8>(), Some(&3));
+
+    let mut tuple = meta_tuple!(#Pair::Mixed(1, 2), 'c');
+    let dynamic: &mut dyn MetaAny = &mut tuple;
+    let (byte, c, int) = dynamic.query_mut::<(&mut u8, &mut char, &mut i32)>().unwrap();
+    *byte += 1;
+    *c = 'd';
+    *int += 1;
+    *byte += 1;
+    assert_eq!(dynamic.get::<u8>(), Some(&4));
+    assert_eq!(dynamic.get::<i32>(), Some(&2));
+    assert_eq!(Pair::None.query_ref::<(&i32,)>(), None);
+
+    let mut pair = Pair::Two(1, 2);
+    let (int, byte) = pair.query_mut::<(&mut i32, Option<&mut u8>)>().unwrap();
+    *int += 1;
+    assert!(byte.is_none());
+    *int += 1;
+    assert_eq!(pair.get_all::<i32>().copied().collect::<Vec<_>>(), [3, 2]);
+
+    let mut tagged = Tagged::Named {
+        int: 1,
+        tag: 2,
+        c: 'c',
+    };
+    let (c, int) = tagged.query_mut::<(&mut char, &mut i32)>().unwrap();
+    *int += 1;
+    *c = 'd';
+    *int += 1;
+    let dynamic: &mut dyn MetaAny = &mut tagged;
+    let (int, c) = dynamic.query_mut::<(&mut i32, &mut char)>().unwrap();
+    *c = 'e';
+    *int += 1;
+    let Tagged::Named { int, tag, c } = tagged;
+    assert_eq!((int, tag, c), (4, 2, 'e'));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn test_enum_all() {
+    let mut pair = Pair::Two(1, 2);
+    let (all,) = pair.query_mut::<(All<&mut i32>,)>().unwrap();
+    let items: Vec<_> = all.collect();
+    assert_eq!(items.len(), 2);
+    for item in items {
+        *item += 1;
+    }
+    assert_eq!(pair.get_all::<i32>().copied().collect::<Vec<_>>(), [2, 3]);
+
+    let mut tuple = meta_tuple!(#Pair::Two(1, 2), 3i32, 'c');
+    let dynamic: &mut dyn MetaAny = &mut tuple;
+    let (all, c) = dynamic.query_mut::<(All<&mut i32>, &mut char)>().unwrap();
+    for item in all {
+        *item *= 10;
+        *c = 'd';
+    }
+    assert_eq!(
+        tuple.get_all::<i32>().copied().collect::<Vec<_>>(),
+        [10, 20, 30]
+    );
+}
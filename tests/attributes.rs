@@ -0,0 +1,177 @@
+use meta_tuple::{MetaAny, MetaBundle, MetaItem, MetaTuple, Take};
+
+#[derive(Debug, PartialEq, MetaTuple)]
+struct Global {
+    seed: u64,
+    scale: f32,
+}
+
+#[derive(Debug, MetaTuple)]
+struct Context {
+    hp: i32,
+    #[meta(flatten)]
+    global: Global,
+    #[meta(skip)]
+    cache: Vec<i32>,
+    #[meta(flatten)]
+    extra: MetaItem<char>,
+}
+
+#[derive(Debug, PartialEq, MetaTuple)]
+#[meta(item)]
+struct Opaque {
+    #[meta(skip)]
+    hp: i32,
+}
+
+fn context() -> Context {
+    Context {
+        hp: 10,
+        global: Global {
+            seed: 1,
+            scale: 2.0,
+        },
+        cache: vec![1, 2, 3],
+        extra: MetaItem('c'),
+    }
+}
+
+#[test]
+pub fn test_flatten() {
+    let mut context = context();
+    assert_eq!(context.get::<u64>(), Some(&1));
+    assert_eq!(context.get::<char>(), Some(&'c'));
+    assert_eq!(context.get::<Global>(), None);
+    assert_eq!(context.get::<Vec<i32>>(), None);
+    assert_eq!(context.len(), 4);
+
+    let (hp, scale, c) = context
+        .query_mut::<(&mut i32, &mut f32, &mut char)>()
+        .unwrap();
+    *hp += 1;
+    *scale += 1.0;
+    *c = 'd';
+    *hp += 1;
+    assert_eq!(context.hp, 12);
+    assert_eq!(context.global.scale, 3.0);
+
+    let dynamic: &mut dyn MetaAny = &mut context;
+    assert_eq!(dynamic.get::<u64>(), Some(&1));
+    *dynamic.get_mut::<u64>().unwrap() += 1;
+    assert_eq!(dynamic.get::<Vec<i32>>(), None);
+    assert_eq!(dynamic.get::<char>(), Some(&'d'));
+    assert_eq!(context.global.seed, 2);
+    assert_eq!(context.cache, [1, 2, 3]);
+
+    assert_eq!(context.field_count(), 3);
+    assert_eq!(context.field_name(2), Some("extra"));
+    assert_eq!(
+        context.get_field_by_name("global").unwrap().downcast_ref(),
+        Some(&Global {
+            seed: 2,
+            scale: 3.0
+        })
+    );
+
+    let (hp, rest) = Take::<i32, _>::take_item(context);
+    assert_eq!(hp, 12);
+    assert_eq!(rest.get::<f32>(), Some(&3.0));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn test_flatten_extend() {
+    let mut vec = meta_tuple::MetaVec::new();
+    vec.extend(context().join(1u8));
+    assert_eq!(vec.len(), 5);
+    assert_eq!(vec.get::<u64>(), Some(&1));
+    assert_eq!(vec.get::<Vec<i32>>(), None);
+}
+
+#[test]
+pub fn test_item() {
+    let mut opaque = Opaque { hp: 1 };
+    assert_eq!(opaque.get::<i32>(), None);
+    opaque.get_mut::<Opaque>().unwrap().hp += 1;
+    let dynamic: &dyn MetaAny = &opaque;
+    assert_eq!(dynamic.get::<Opaque>(), Some(&Opaque { hp: 2 }));
+    assert_eq!(dynamic.get::<i32>(), None);
+}
+
+#[derive(Debug, PartialEq, MetaTuple)]
+#[meta(item)]
+struct Labeled {
+    hp: i32,
+    #[meta(skip)]
+    label: &'static str,
+    #[meta(flatten)]
+    global: Global,
+}
+
+#[derive(Debug, PartialEq, MetaTuple)]
+#[meta(item)]
+enum Shape {
+    Circle(f64),
+    Empty,
+}
+
+#[test]
+pub fn test_item_with_fields() {
+    let mut labeled = Labeled {
+        hp: 1,
+        label: "a",
+        global: Global {
+            seed: 2,
+            scale: 3.0,
+        },
+    };
+    assert_eq!(labeled.get::<Labeled>().map(|x| x.hp), Some(1));
+    assert_eq!(labeled.get::<u64>(), Some(&2));
+    assert_eq!(labeled.get::<&str>(), None);
+    assert_eq!(labeled.get_mut::<i32>(), None);
+    assert_eq!(labeled.get_mut::<Labeled>(), None);
+    assert_eq!(labeled.query_mut::<(&Labeled, &mut i32)>(), None);
+    let (item, hp) = labeled.query_ref::<(&Labeled, &i32)>().unwrap();
+    assert_eq!(item.label, "a");
+    assert_eq!(hp, &1);
+    assert_eq!(labeled.len(), 4);
+
+    let dynamic: &mut dyn MetaAny = &mut labeled;
+    assert_eq!(dynamic.get::<f32>(), Some(&3.0));
+    assert_eq!(dynamic.get::<Labeled>().map(|x| x.hp), Some(1));
+    assert_eq!(dynamic.get_mut::<i32>(), None);
+    let mut names = Vec::new();
+    dynamic.visit(&mut |_, name| names.push(name));
+    assert_eq!(names, ["attributes::Labeled", "i32", "u64", "f32"]);
+
+    let (item, rest) = Take::<Labeled, _>::take_item(labeled);
+    assert_eq!(item.hp, 1);
+    assert_eq!(rest.len(), 0);
+
+    let shape = Shape::Circle(1.0);
+    assert_eq!(shape.get::<f64>(), Some(&1.0));
+    assert_eq!(shape.get::<Shape>(), Some(&Shape::Circle(1.0)));
+    assert_eq!(Shape::Empty.get::<f64>(), None);
+    assert_eq!(Shape::Empty.len(), 1);
+}
+
+#[test]
+pub fn test_flatten_erased() {
+    let mut context = context();
+    let dynamic: &mut dyn MetaAny = &mut context;
+    assert!(matches!(
+        dynamic.as_erased(),
+        meta_tuple::ErasedInner::Struct(_)
+    ));
+    let (hp, scale) = dynamic.query_mut::<(&mut i32, &mut f32)>().unwrap();
+    *hp += 1;
+    *scale += 1.0;
+    assert_eq!(
+        dynamic.query_ref::<(&i32, &f32, &char)>(),
+        Some((&11, &3.0, &'c'))
+    );
+    assert_eq!(dynamic.len(), 4);
+
+    let (_, rest) = Take::<i32, _>::take_item(context);
+    assert_eq!(rest.get::<Vec<i32>>(), None);
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "alloc")]
+use meta_tuple::{All, DynMetaTuple, MetaAny, MetaTuple, MetaVec, meta_tuple};
+
+pub trait Component {
+    fn play(&self, input: &dyn MetaAny) -> Box<dyn MetaAny>;
+}
+
+pub struct Attack;
+
+impl Component for Attack {
+    fn play(&self, input: &dyn MetaAny) -> Box<dyn MetaAny> {
+        let power = input.get::<i32>().unwrap();
+        meta_tuple!(*power * 2, 'a').into_dyn()
+    }
+}
+
+fn sum(tuple: &impl MetaTuple) -> i32 {
+    tuple.get_all::<i32>().sum()
+}
+
+#[test]
+pub fn test_boxed() {
+    let components: Vec<Box<dyn Component>> = vec![Box::new(Attack)];
+    let out = components[0].play(&meta_tuple!(2i32));
+    assert_eq!(out.get::<i32>(), Some(&4));
+
+    let mut joined = meta_tuple!(#out, 1.0f32);
+    let (int, float, c) = joined.query_mut::<(&mut i32, &mut f32, &char)>().unwrap();
+    *int += 1;
+    *float += 1.0;
+    assert_eq!(c, &'a');
+    assert_eq!(joined.query_ref::<(&i32, &f32)>(), Some((&5, &2.0)));
+    assert_eq!(sum(&joined), 5);
+
+    let mut boxed = DynMetaTuple::from_box(joined.join(3i32).into_dyn());
+    assert_eq!(boxed.get::<char>(), Some(&'a'));
+    let (all, float) = boxed.query_mut::<(All<&mut i32>, &mut f32)>().unwrap();
+    for int in all {
+        *int += 1;
+        *float += 1.0;
+    }
+    assert_eq!(sum(&boxed), 10);
+    assert_eq!(boxed.get::<f32>(), Some(&4.0));
+}
+
+#[test]
+pub fn test_boxed_static() {
+    let mut tuple = Box::new(meta_tuple!(1i32, 'c'));
+    *tuple.get_mut::<i32>().unwrap() += 1;
+    let (int, c) = tuple.query_mut::<(&mut i32, &mut char)>().unwrap();
+    *int += 1;
+    *c = 'd';
+    assert_eq!(tuple.query_ref::<(&i32, &char)>(), Some((&3, &'d')));
+
+    let mut vec = MetaVec::new();
+    vec.extend(tuple);
+    assert_eq!(vec.len(), 2);
+    assert_eq!(vec.get::<char>(), Some(&'d'));
+}
+
+#[derive(Debug, PartialEq, meta_tuple::MetaTuple)]
+struct Bundle {
+    int: i32,
+    name: String,
+}
+
+#[test]
+pub fn test_boxed_into_sink() {
+    let float = 1.0f32;
+    let boxed = meta_tuple!(
+        #Bundle {
+            int: 1,
+            name: String::from("a"),
+        },
+        'c',
+        &float
+    )
+    .into_dyn();
+    let mut vec = MetaVec::new();
+    vec.extend(boxed);
+    assert_eq!(vec.len(), 3);
+    assert_eq!(vec.get::<String>().map(String::as_str), Some("a"));
+    assert_eq!(vec.get::<f32>(), None);
+
+    let list: Vec<Box<dyn MetaAny>> = vec![
+        meta_tuple!(2i32).into_dyn(),
+        DynMetaTuple::from_box(meta_tuple!(3i32, 'd').into_dyn()).into_dyn(),
+    ];
+    vec.extend(list);
+    assert_eq!(vec.get_all::<i32>().copied().collect::<Vec<_>>(), [1, 2, 3]);
+    assert_eq!(vec.get_all::<char>().copied().collect::<Vec<_>>(), ['c', 'd']);
+
+    let mut names = Vec::new();
+    vec.visit(&mut |_, name| names.push(name));
+    assert_eq!(names[4], "i32");
+}
@@ -0,0 +1,163 @@
+use core::any::{Any, TypeId};
+use meta_tuple::{
+    ErasedBundlePtr, ErasedInner, ErasedInnerMut, ErasedInnerPtr, MetaAny, MetaBundle, MetaTuple,
+};
+
+#[derive(Debug, MetaTuple)]
+struct Stats {
+    hp: i32,
+    mana: u32,
+    r#type: &'static str,
+}
+
+#[derive(Debug, MetaTuple)]
+struct Pair(i32, char);
+
+#[derive(Debug, MetaTuple)]
+struct Unit;
+
+/// Written by hand, relies on the default `visit` and `visit_mut`.
+struct Manual {
+    hp: i32,
+    name: String,
+}
+
+impl MetaBundle for Manual {
+    fn get_field(&self, idx: usize) -> Option<&dyn Any> {
+        match idx {
+            0 => Some(&self.hp),
+            1 => Some(&self.name),
+            _ => None,
+        }
+    }
+
+    fn get_field_mut(&mut self, idx: usize) -> Option<&mut dyn Any> {
+        match idx {
+            0 => Some(&mut self.hp),
+            1 => Some(&mut self.name),
+            _ => None,
+        }
+    }
+
+    fn field_type_name(&self, idx: usize) -> Option<&'static str> {
+        (idx == 0).then_some("i32")
+    }
+
+    unsafe fn get_field_ptr(this: *mut Self, idx: usize) -> Option<(*mut (), TypeId)> {
+        match idx {
+            0 => Some((unsafe { &raw mut (*this).hp }.cast(), TypeId::of::<i32>())),
+            1 => Some((unsafe { &raw mut (*this).name }.cast(), TypeId::of::<String>())),
+            _ => None,
+        }
+    }
+}
+
+unsafe impl MetaAny for Manual {
+    fn as_erased(&self) -> ErasedInner<'_> {
+        ErasedInner::Struct(self)
+    }
+
+    fn as_erased_mut(&mut self) -> ErasedInnerMut<'_> {
+        ErasedInnerMut::Struct(self)
+    }
+
+    unsafe fn as_erased_ptr(&self, this: *mut ()) -> ErasedInnerPtr<'_> {
+        ErasedInnerPtr::Struct(unsafe { ErasedBundlePtr::new(this.cast::<Self>()) })
+    }
+}
+
+/// What a scripting console would print for a struct backed tuple.
+fn describe(bundle: &dyn MetaBundle) -> Vec<(Option<&'static str>, Option<&'static str>)> {
+    (0..bundle.field_count())
+        .map(|idx| (bundle.field_name(idx), bundle.field_type_name(idx)))
+        .collect()
+}
+
+#[test]
+pub fn test_named() {
+    let mut stats = Stats {
+        hp: 10,
+        mana: 5,
+        r#type: "mage",
+    };
+    assert_eq!(stats.field_count(), 3);
+    assert_eq!(
+        describe(&stats),
+        [
+            (Some("hp"), Some("i32")),
+            (Some("mana"), Some("u32")),
+            (Some("type"), Some("&str")),
+        ]
+    );
+    assert_eq!(stats.field_type_id(1), Some(TypeId::of::<u32>()));
+    assert_eq!(stats.field_type_id(3), None);
+    assert_eq!(stats.field_name(3), None);
+
+    let hp = stats.get_field_by_name("hp").unwrap();
+    assert_eq!(hp.downcast_ref::<i32>(), Some(&10));
+    assert!(stats.get_field_by_name("speed").is_none());
+    *stats
+        .get_field_by_name_mut("mana")
+        .and_then(<dyn Any>::downcast_mut::<u32>)
+        .unwrap() += 1;
+    assert_eq!(stats.mana, 6);
+
+    let dynamic: &dyn MetaAny = &stats;
+    let ErasedInner::Struct(bundle) = dynamic.as_erased() else {
+        panic!()
+    };
+    assert_eq!(
+        bundle.get_field_by_name("type").unwrap().downcast_ref(),
+        Some(&"mage")
+    );
+}
+
+#[test]
+pub fn test_unnamed() {
+    let mut pair = Pair(1, 'c');
+    assert_eq!(
+        describe(&pair),
+        [(Some("0"), Some("i32")), (Some("1"), Some("char"))]
+    );
+    *pair
+        .get_field_by_name_mut("1")
+        .and_then(<dyn Any>::downcast_mut::<char>)
+        .unwrap() = 'd';
+    assert_eq!(pair.1, 'd');
+
+    assert_eq!(Unit.field_count(), 0);
+    assert!(Unit.get_field_by_name("0").is_none());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn test_vec() {
+    let mut vec = meta_tuple::MetaVec::new();
+    vec.push(1i32);
+    vec.push('c');
+    assert_eq!(describe(&vec), [(None, Some("i32")), (None, Some("char"))]);
+    assert_eq!(vec.field_type_id(1), Some(TypeId::of::<char>()));
+    assert!(vec.get_field_by_name("0").is_none());
+}
+
+#[test]
+pub fn test_default_visit() {
+    let mut manual = Manual {
+        hp: 1,
+        name: String::from("a"),
+    };
+    let mut names = Vec::new();
+    manual.visit(&mut |_, name| names.push(name));
+    assert_eq!(names, ["i32", "_"]);
+    manual.visit_mut(&mut |item, _| {
+        if let Some(hp) = item.downcast_mut::<i32>() {
+            *hp += 1;
+        }
+    });
+    assert_eq!(manual.hp, 2);
+    assert_eq!(manual.len(), 2);
+
+    let dynamic: &dyn MetaAny = &manual;
+    assert_eq!(dynamic.get::<String>(), Some(&String::from("a")));
+    assert_eq!(dynamic.count::<i32>(), 1);
+}
@@ -0,0 +1,78 @@
+use std::cell::{Ref, RefCell, RefMut};
+
+use meta_tuple::{MetaAny, MetaQuery, MetaTuple, impl_meta_any, meta_tuple};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Health(u32);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Damage(u32);
+
+#[derive(MetaQuery)]
+pub struct AttackQuery<'t> {
+    pub damage: &'t Damage,
+    pub hp: RefMut<'t, Health>,
+}
+
+fn attack(input: &impl MetaTuple) {
+    let mut query = input.query_ref::<AttackQuery>().unwrap();
+    query.hp.0 -= query.damage.0;
+}
+
+pub trait Dyn: MetaAny {}
+
+impl<T: MetaAny> Dyn for T {}
+
+impl_meta_any!(Dyn);
+
+#[test]
+pub fn test_ref_cell() {
+    let tuple = meta_tuple!(RefCell::new(Health(10)), Damage(3));
+    attack(&tuple);
+    assert_eq!(*tuple.borrow::<Health>().unwrap(), Health(7));
+
+    *tuple.borrow_mut::<Health>().unwrap() = Health(5);
+    assert_eq!(tuple.borrow::<Health>().unwrap().0, 5);
+    assert!(tuple.borrow::<Damage>().is_none());
+
+    let (hp, damage) = tuple.query_ref::<(Ref<Health>, &Damage)>().unwrap();
+    assert_eq!((hp.0, damage.0), (5, 3));
+    assert!(tuple.query_ref::<(RefMut<Health>,)>().is_none());
+    drop(hp);
+
+    let reference = meta_tuple!(Damage(1)).join_tuple(&tuple);
+    attack(&reference);
+    assert_eq!(tuple.borrow::<Health>().unwrap().0, 4);
+
+    let dynamic: &dyn Dyn = &tuple;
+    *dynamic.borrow_mut::<Health>().unwrap() = Health(1);
+    assert_eq!(*dynamic.borrow::<Health>().unwrap(), Health(1));
+    assert!(dynamic.borrow_mut::<Damage>().is_none());
+}
+
+#[test]
+#[should_panic]
+pub fn test_ref_cell_conflict() {
+    let tuple = meta_tuple!(RefCell::new(Health(10)));
+    let _hp = tuple.borrow::<Health>();
+    let _ = tuple.borrow_mut::<Health>();
+}
+
+#[cfg(feature = "std")]
+#[test]
+pub fn test_lock() {
+    use std::sync::{Mutex, RwLock};
+
+    let tuple = meta_tuple!(Mutex::new(Health(10)), RwLock::new(Damage(3)));
+    tuple.lock::<Health>().unwrap().0 -= tuple.read::<Damage>().unwrap().0;
+    assert_eq!(*tuple.lock::<Health>().unwrap(), Health(7));
+    tuple.write::<Damage>().unwrap().0 = 4;
+    assert_eq!(*tuple.read::<Damage>().unwrap(), Damage(4));
+    assert!(tuple.lock::<Damage>().is_none());
+    assert!(tuple.read::<Health>().is_none());
+
+    let dynamic: &dyn Dyn = &tuple;
+    dynamic.lock::<Health>().unwrap().0 = 1;
+    assert_eq!(*dynamic.lock::<Health>().unwrap(), Health(1));
+    assert_eq!(*dynamic.write::<Damage>().unwrap(), Damage(4));
+}
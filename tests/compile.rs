@@ -0,0 +1,7 @@
+#![cfg(not(miri))]
+
+#[test]
+pub fn test_compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
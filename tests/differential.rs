@@ -0,0 +1,192 @@
+use core::any::type_name;
+use meta_tuple::{
+    DynMetaTuple, MetaAny, MetaError, MetaItem, MetaTuple, meta_tuple, meta_tuple_type,
+    pipeline::Maybe,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Zst;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, MetaItem)]
+struct Marker;
+
+#[derive(Debug, MetaTuple)]
+struct Bundle {
+    unit: (),
+    zst: Zst,
+    int: i32,
+}
+
+#[derive(Debug, MetaTuple)]
+struct Nested {
+    #[meta(flatten)]
+    bundle: Bundle,
+    #[meta(skip)]
+    _hidden: i32,
+    unit: (),
+    #[meta(flatten)]
+    tuple: meta_tuple_type!(Zst, char),
+}
+
+/// Owned results of every lookup of `T`.
+#[derive(Debug, PartialEq)]
+struct Lookup<T> {
+    get: Option<T>,
+    get_mut: Option<T>,
+    contains: bool,
+    count: usize,
+    all: Vec<T>,
+    /// `dyn MetaAny` only finds every mutable item with the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    all_mut: Vec<T>,
+    query_ref: Option<T>,
+    query_mut: Option<T>,
+    try_get_mut: Result<T, MetaError>,
+}
+
+fn lookup<T: Clone + 'static>(tuple: &mut impl MetaTuple) -> Lookup<T> {
+    Lookup {
+        get: tuple.get::<T>().cloned(),
+        get_mut: tuple.get_mut::<T>().cloned(),
+        contains: tuple.contains::<T>(),
+        count: tuple.count::<T>(),
+        all: tuple.get_all::<T>().cloned().collect(),
+        #[cfg(feature = "alloc")]
+        all_mut: tuple.get_all_mut::<T>().map(|x| x.clone()).collect(),
+        query_ref: tuple.query_ref::<(&T,)>().map(|(x,)| x.clone()),
+        query_mut: tuple.query_mut::<(&mut T,)>().map(|(x,)| x.clone()),
+        try_get_mut: tuple.try_get_mut::<T>().cloned(),
+    }
+}
+
+fn lookup_dyn<T: Clone + 'static>(tuple: &mut dyn MetaAny) -> Lookup<T> {
+    Lookup {
+        get: tuple.get::<T>().cloned(),
+        get_mut: tuple.get_mut::<T>().cloned(),
+        contains: tuple.contains::<T>(),
+        count: tuple.count::<T>(),
+        all: tuple.get_all::<T>().cloned().collect(),
+        #[cfg(feature = "alloc")]
+        all_mut: tuple.get_all_mut::<T>().map(|x| x.clone()).collect(),
+        query_ref: tuple.try_query_ref::<(&T,)>().ok().map(|(x,)| x.clone()),
+        query_mut: tuple
+            .try_query_mut::<(&mut T,)>()
+            .ok()
+            .map(|(x,)| x.clone()),
+        try_get_mut: tuple.try_get_mut::<T>().cloned(),
+    }
+}
+
+/// Assert the static and the `Box<dyn MetaAny>` path agree on `tuple`.
+fn check<M: MetaTuple>(mut tuple: M) {
+    let expected = (
+        lookup::<()>(&mut tuple),
+        lookup::<Zst>(&mut tuple),
+        lookup::<Marker>(&mut tuple),
+        lookup::<i32>(&mut tuple),
+        lookup::<char>(&mut tuple),
+    );
+    let mut boxed: Box<dyn MetaAny + '_> = Box::new(tuple);
+    let actual = (
+        lookup_dyn::<()>(&mut *boxed),
+        lookup_dyn::<Zst>(&mut *boxed),
+        lookup_dyn::<Marker>(&mut *boxed),
+        lookup_dyn::<i32>(&mut *boxed),
+        lookup_dyn::<char>(&mut *boxed),
+    );
+    assert_eq!(expected, actual, "{}", type_name::<M>());
+}
+
+#[test]
+pub fn test_unit() {
+    let mut tuple = meta_tuple!(1i32);
+    assert_eq!(tuple.get::<()>(), None);
+    assert_eq!(tuple.get_mut::<()>(), None);
+    assert_eq!(tuple.query_ref::<(&(),)>(), None);
+    assert_eq!(tuple.query_ref::<()>(), Some(()));
+
+    let dynamic: &mut dyn MetaAny = &mut tuple;
+    assert_eq!(dynamic.get::<()>(), None);
+    assert_eq!(dynamic.get_mut::<()>(), None);
+    assert!(dynamic.try_query_mut::<(&mut (),)>().is_err());
+
+    let mut tuple = meta_tuple!((), Zst);
+    assert_eq!(tuple.get::<()>(), Some(&()));
+    assert_eq!(tuple.get_mut::<Zst>(), Some(&mut Zst));
+    assert_eq!(tuple.count::<()>(), 1);
+}
+
+#[test]
+pub fn test_differential() {
+    let mut unit = ();
+    let mut zst = Zst;
+    let mut int = 1i32;
+    let mut inner: Box<dyn MetaAny> = Box::new(meta_tuple!((), 4i32));
+
+    check(());
+    check(meta_tuple!(()));
+    check(meta_tuple!(Zst));
+    check(Marker);
+    check(MetaItem(Marker));
+    check(meta_tuple!(1i32, (), Zst, 'c', 2i32, (), Marker));
+    check(meta_tuple!(&(), &Zst, &int, 'c'));
+    check(meta_tuple!(&mut unit, &mut zst, &mut int, (), Zst));
+    check(meta_tuple!(#Some(()), #None::<Zst>, #Some(1i32), #Some(Marker)));
+    check(Bundle {
+        unit: (),
+        zst: Zst,
+        int: 1,
+    });
+    check(meta_tuple!(#Bundle { unit: (), zst: Zst, int: 1 }, (), 2i32));
+    check(Nested {
+        bundle: Bundle {
+            unit: (),
+            zst: Zst,
+            int: 1,
+        },
+        _hidden: 2,
+        unit: (),
+        tuple: meta_tuple!(Zst, 'c'),
+    });
+    check(Maybe(Some(meta_tuple!((), Zst, 1i32))));
+    check(Maybe(None::<MetaItem<()>>));
+    check(meta_tuple!(#DynMetaTuple::from_mut(&mut *inner), Zst, 'c'));
+    check(meta_tuple!(#DynMetaTuple::from_ref(&*inner), ()));
+
+    check([meta_tuple!((), 1i32), meta_tuple!((), 2i32)]);
+    check([meta_tuple!(Zst, 1i32, ()), meta_tuple!(Zst, 2i32, ())]);
+    check([Marker; 0]);
+    check(
+        &[
+            &meta_tuple!(1i32, ()) as &dyn MetaAny,
+            &Bundle {
+                unit: (),
+                zst: Zst,
+                int: 2,
+            },
+        ][..],
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn test_heap() {
+    check(vec![
+        meta_tuple!(Zst).into_dyn(),
+        Box::new(Marker),
+        meta_tuple!((), 'c').into_dyn(),
+    ]);
+
+    let mut vec = meta_tuple::MetaVec::new();
+    vec.push(());
+    vec.push(Zst);
+    vec.push(1i32);
+    vec.push(());
+    check(vec);
+
+    let mut map = meta_tuple::MetaMap::new();
+    map.insert(());
+    map.insert(Marker);
+    map.insert('c');
+    check(map);
+}
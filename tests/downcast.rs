@@ -0,0 +1,85 @@
+#![cfg(feature = "alloc")]
+use meta_tuple::{
+    DowncastBox, MetaAny, MetaItem, MetaTuple, MetaVec, impl_meta_any, meta_tuple, meta_tuple_type,
+    pipeline::Maybe,
+};
+
+#[derive(Debug, PartialEq, MetaTuple)]
+struct Bundle {
+    int: i32,
+    float: f32,
+}
+
+#[derive(Debug, PartialEq, MetaItem)]
+struct Marker;
+
+pub trait Plugin: MetaAny + Send {}
+
+impl<T: MetaAny + Send> Plugin for T {}
+
+impl_meta_any!(Plugin);
+
+#[test]
+pub fn test_downcast() {
+    let mut boxed: Box<dyn MetaAny> = meta_tuple!(1i32, 'c').into_dyn();
+    assert!(boxed.is::<meta_tuple_type!(i32, char)>());
+    assert!(!boxed.is::<meta_tuple_type!(char, i32)>());
+    assert!(!boxed.is::<MetaItem<i32>>());
+
+    let tuple = boxed.downcast_mut::<meta_tuple_type!(i32, char)>().unwrap();
+    tuple.0.0 += 1;
+    let tuple = boxed.downcast_ref::<meta_tuple_type!(i32, char)>().unwrap();
+    assert_eq!(tuple.get::<i32>(), Some(&2));
+
+    let boxed = boxed.downcast::<meta_tuple_type!(char)>().unwrap_err();
+    let Ok(tuple) = boxed.downcast::<meta_tuple_type!(i32, char)>() else {
+        panic!()
+    };
+    assert_eq!(tuple.get::<char>(), Some(&'c'));
+
+    let bundle: &dyn MetaAny = &Bundle { int: 1, float: 2.0 };
+    assert_eq!(
+        bundle.downcast_ref::<Bundle>(),
+        Some(&Bundle { int: 1, float: 2.0 })
+    );
+    let marker: &dyn MetaAny = &Marker;
+    assert_eq!(marker.downcast_ref::<Marker>(), Some(&Marker));
+
+    let joined: &dyn MetaAny = &meta_tuple!(#Bundle { int: 1, float: 2.0 }, #Marker, #Some(1u8));
+    assert!(joined.is::<meta_tuple_type!(#Bundle, #Marker, #Option<u8>)>());
+
+    let maybe: &dyn MetaAny = &Maybe(None::<MetaItem<i32>>);
+    assert!(maybe.is::<Maybe<MetaItem<i32>>>());
+
+    let vec: &dyn MetaAny = &MetaVec::new();
+    assert!(vec.is::<MetaVec>());
+    let unit: &dyn MetaAny = &();
+    assert!(unit.is::<()>());
+}
+
+#[test]
+pub fn test_non_static() {
+    let int = 1i32;
+    let tuple = meta_tuple!(&int, 'c');
+    let dynamic: &dyn MetaAny = &tuple;
+    assert_eq!(dynamic.meta_type_id(), None);
+    assert!(!dynamic.is::<meta_tuple_type!(&i32, char)>());
+
+    let boxed: Box<dyn MetaAny> = meta_tuple!(1i32).into_dyn();
+    let nested: &dyn MetaAny = &meta_tuple!(#boxed);
+    assert_eq!(nested.meta_type_id(), None);
+}
+
+#[test]
+pub fn test_subtrait() {
+    let mut plugin: Box<dyn Plugin> = Box::new(meta_tuple!(1i32, 'c'));
+    let tuple = plugin
+        .downcast_mut::<meta_tuple_type!(i32, char)>()
+        .unwrap();
+    tuple.1.0 = 'd';
+    assert!(plugin.is::<meta_tuple_type!(i32, char)>());
+    let Ok(tuple) = plugin.downcast::<meta_tuple_type!(i32, char)>() else {
+        panic!()
+    };
+    assert_eq!(tuple.get::<char>(), Some(&'d'));
+}
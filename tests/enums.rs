@@ -0,0 +1,147 @@
+use core::any::TypeId;
+use meta_tuple::{ErasedInner, MetaAny, MetaBundle, MetaQuery, MetaTuple, meta_tuple};
+
+#[derive(Debug, PartialEq)]
+struct Unit(&'static str);
+
+#[derive(Debug, PartialEq)]
+struct Zone(u32);
+
+#[derive(Debug, PartialEq)]
+struct Radius(f32);
+
+#[derive(Debug, PartialEq, MetaTuple)]
+enum Target {
+    Single(Unit),
+    Area(Zone, Radius),
+    Line {
+        from: Zone,
+        #[meta(skip)]
+        _length: u32,
+        to: Zone,
+    },
+    None,
+}
+
+#[derive(Debug, PartialEq, MetaTuple)]
+#[meta(item)]
+enum Effect {
+    Heal(#[meta(skip)] i32),
+    Damage {
+        #[meta(skip)]
+        amount: i32,
+    },
+}
+
+#[derive(Debug, MetaQuery)]
+enum Aim<'t> {
+    Single(&'t mut Unit),
+    Area {
+        zone: &'t Zone,
+        radius: &'t mut Radius,
+    },
+}
+
+#[test]
+pub fn test_static() {
+    let mut target = Target::Area(Zone(1), Radius(2.0));
+    assert_eq!(target.get::<Zone>(), Some(&Zone(1)));
+    assert_eq!(target.get::<Unit>(), None);
+    target.get_mut::<Radius>().unwrap().0 += 1.0;
+    assert_eq!(target, Target::Area(Zone(1), Radius(3.0)));
+
+    let (zone, radius) = target.query_mut::<(&mut Zone, &mut Radius)>().unwrap();
+    zone.0 += 1;
+    radius.0 += 1.0;
+    zone.0 += 1;
+    assert_eq!(target, Target::Area(Zone(3), Radius(4.0)));
+
+    let mut line = Target::Line {
+        from: Zone(1),
+        _length: 5,
+        to: Zone(2),
+    };
+    assert_eq!(line.get::<u32>(), None);
+    assert_eq!(
+        line.get_all::<Zone>().collect::<Vec<_>>(),
+        [&Zone(1), &Zone(2)]
+    );
+    for zone in line.get_all_mut::<Zone>() {
+        zone.0 *= 10;
+    }
+    assert_eq!(line.get_nth::<Zone>(1), Some(&Zone(20)));
+    assert_eq!(Target::None.get::<Zone>(), None);
+    assert_eq!(Target::None.get_all::<Zone>().count(), 0);
+
+    #[cfg(feature = "alloc")]
+    {
+        let mut vec = meta_tuple::MetaVec::new();
+        vec.extend(Target::Single(Unit("a")));
+        assert_eq!(vec.get::<Unit>(), Some(&Unit("a")));
+    }
+}
+
+#[test]
+pub fn test_dynamic() {
+    let mut target = Target::Single(Unit("a"));
+    let dynamic: &mut dyn MetaAny = &mut target;
+    assert!(dynamic.is::<Target>());
+    dynamic.get_mut::<Unit>().unwrap().0 = "b";
+    assert_eq!(dynamic.get::<Zone>(), None);
+    let (unit, zone) = dynamic.query_mut::<(&mut Unit, Option<&Zone>)>().unwrap();
+    unit.0 = "c";
+    assert_eq!(zone, None);
+    assert_eq!(target, Target::Single(Unit("c")));
+
+    let target = Target::Line {
+        from: Zone(1),
+        _length: 5,
+        to: Zone(2),
+    };
+    let dynamic: &dyn MetaAny = &target;
+    let ErasedInner::Struct(bundle) = dynamic.as_erased() else {
+        panic!()
+    };
+    assert_eq!(bundle.field_count(), 2);
+    assert_eq!(bundle.field_name(1), Some("to"));
+    assert_eq!(bundle.field_type_id(0), Some(TypeId::of::<Zone>()));
+    assert_eq!(
+        bundle.get_field_by_name("to").unwrap().downcast_ref(),
+        Some(&Zone(2))
+    );
+    assert_eq!(Target::None.field_count(), 0);
+    assert_eq!(Target::Area(Zone(1), Radius(1.0)).field_name(1), Some("1"));
+}
+
+#[test]
+pub fn test_item() {
+    let mut effect = Effect::Heal(1);
+    assert_eq!(effect.get::<i32>(), None);
+    *effect.get_mut::<Effect>().unwrap() = Effect::Damage { amount: 2 };
+    let dynamic: &dyn MetaAny = &effect;
+    assert_eq!(dynamic.get::<Effect>(), Some(&Effect::Damage { amount: 2 }));
+}
+
+#[test]
+pub fn test_query() {
+    let mut area = meta_tuple!(Zone(1), Radius(2.0));
+    let Aim::Area { zone, radius } = area.query_mut::<Aim>().unwrap() else {
+        panic!()
+    };
+    radius.0 += zone.0 as f32;
+    assert_eq!(area.get::<Radius>(), Some(&Radius(3.0)));
+
+    let mut both = meta_tuple!(Zone(1), Radius(2.0), Unit("a"));
+    let Some(Aim::Single(unit)) = both.query_mut::<Aim>() else {
+        panic!()
+    };
+    unit.0 = "b";
+    assert_eq!(both.get::<Unit>(), Some(&Unit("b")));
+
+    let dynamic: &mut dyn MetaAny = &mut area;
+    assert!(matches!(dynamic.query_mut::<Aim>(), Some(Aim::Area { .. })));
+    let zone = meta_tuple!(Zone(1));
+    assert!(zone.query_ref::<Aim>().is_none());
+    assert!(zone.try_query_ref::<Aim>().is_err());
+    assert!(Aim::validate());
+}
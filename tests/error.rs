@@ -0,0 +1,118 @@
+#![cfg(feature = "alloc")]
+use meta_tuple::{MetaAny, MetaError, MetaQuery, MetaTuple, impl_meta_any, meta_tuple};
+
+#[derive(Debug, MetaQuery)]
+pub struct Query<'t> {
+    pub int: &'t i32,
+    pub float: &'t mut f32,
+    pub string: &'t String,
+}
+
+#[derive(Debug, MetaQuery)]
+pub struct AliasedQuery<'t, A, B> {
+    pub a: &'t A,
+    pub b: &'t mut B,
+}
+
+pub trait Dyn: MetaAny {}
+
+impl<T: MetaAny> Dyn for T {}
+
+impl_meta_any!(Dyn);
+
+#[test]
+pub fn test_try_get() {
+    let c = 'c';
+    let mut tuple = meta_tuple!(1i32, &c);
+    assert_eq!(tuple.try_get::<i32>(), Ok(&1));
+    assert_eq!(
+        tuple.try_get::<f32>(),
+        Err(MetaError::Missing { type_name: "f32" })
+    );
+    *tuple.try_get_mut::<i32>().unwrap() = 2;
+    assert_eq!(tuple.try_get::<i32>(), Ok(&2));
+    assert_eq!(
+        tuple.try_get_mut::<char>(),
+        Err(MetaError::NotMutable { type_name: "char" })
+    );
+    assert_eq!(
+        tuple.try_get_mut::<f32>(),
+        Err(MetaError::Missing { type_name: "f32" })
+    );
+
+    let dynamic: &mut dyn Dyn = &mut tuple;
+    assert_eq!(dynamic.try_get::<i32>(), Ok(&2));
+    assert_eq!(
+        dynamic.try_get::<f32>(),
+        Err(MetaError::Missing { type_name: "f32" })
+    );
+    *dynamic.try_get_mut::<i32>().unwrap() = 3;
+    assert_eq!(
+        dynamic.try_get_mut::<char>(),
+        Err(MetaError::NotMutable { type_name: "char" })
+    );
+    assert_eq!(tuple.get::<i32>(), Some(&3));
+}
+
+#[test]
+pub fn test_try_query() {
+    let mut tuple = meta_tuple!(1i32, 2.0f32);
+    assert_eq!(tuple.try_query_ref::<(&i32, &f32)>().unwrap(), (&1, &2.0));
+    assert_eq!(
+        tuple.try_query_ref::<(&i32, &mut f32)>().unwrap_err(),
+        MetaError::NotMutable { type_name: "f32" }
+    );
+    let (int, float) = tuple.try_query_mut::<(&i32, &mut f32)>().unwrap();
+    *float += *int as f32;
+    assert_eq!(tuple.get::<f32>(), Some(&3.0));
+    assert_eq!(
+        tuple.try_query_mut::<(&mut i32, &i32)>().unwrap_err(),
+        MetaError::Aliased {
+            a: "&mut i32",
+            b: "&i32"
+        }
+    );
+
+    assert_eq!(
+        tuple.try_query_mut::<Query>().unwrap_err(),
+        MetaError::Missing {
+            type_name: "alloc::string::String"
+        }
+    );
+    let mut empty = meta_tuple!('c');
+    assert_eq!(
+        empty.try_query_mut::<Query>().unwrap_err(),
+        MetaError::Many(vec![
+            MetaError::Missing { type_name: "i32" },
+            MetaError::Missing { type_name: "f32" },
+            MetaError::Missing {
+                type_name: "alloc::string::String"
+            },
+        ])
+    );
+    assert_eq!(
+        empty.try_query_ref::<Query>().unwrap_err().to_string(),
+        "missing item i32, missing item f32, missing item alloc::string::String"
+    );
+    assert!(matches!(
+        tuple.try_query_mut::<AliasedQuery<i32, i32>>(),
+        Err(MetaError::Aliased { .. })
+    ));
+
+    let dynamic: &mut dyn Dyn = &mut tuple;
+    assert_eq!(dynamic.try_query_ref::<(&i32, &f32)>().unwrap(), (&1, &3.0));
+    assert_eq!(
+        dynamic.try_query_ref::<(&char, &f32, &u8)>().unwrap_err(),
+        MetaError::Many(vec![
+            MetaError::Missing { type_name: "char" },
+            MetaError::Missing { type_name: "u8" },
+        ])
+    );
+    let (int, float) = dynamic.try_query_mut::<(&i32, &mut f32)>().unwrap();
+    *float += *int as f32;
+    assert!(matches!(
+        dynamic.try_query_mut::<AliasedQuery<i32, i32>>(),
+        Err(MetaError::Aliased { .. })
+    ));
+    assert_eq!(tuple.get::<f32>(), Some(&4.0));
+}
@@ -0,0 +1,49 @@
+use meta_tuple::{MetaTuple, meta_tuple};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Attacker(pub usize);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Defender(pub usize);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DamageDealt(pub usize);
+
+fn attack(attacker: &Attacker, defender: &Defender) -> DamageDealt {
+    DamageDealt(attacker.0.saturating_sub(defender.0))
+}
+
+fn buff(attacker: &mut Attacker, bonus: Option<&usize>) {
+    attacker.0 += bonus.copied().unwrap_or(1);
+}
+
+#[test]
+pub fn test_call() {
+    let mut tuple = meta_tuple!(Attacker(10), Defender(4));
+    assert_eq!(tuple.call(attack), Some(DamageDealt(6)));
+    assert_eq!(tuple.call(buff), Some(()));
+    assert_eq!(tuple.get::<Attacker>(), Some(&Attacker(11)));
+    assert_eq!(tuple.call(|x: &DamageDealt| x.0), None);
+    assert_eq!(tuple.call(|| 1), Some(1));
+
+    let mut count = 0;
+    tuple.call(|a: &mut Attacker, d: &Defender, _: &DamageDealt| {
+        count += 1;
+        a.0 + d.0
+    });
+    assert_eq!(count, 0);
+
+    let tuple = tuple.call_join(attack).call_join(|x: &DamageDealt| x.0 * 2);
+    assert_eq!(tuple.get::<DamageDealt>(), Some(&DamageDealt(7)));
+    assert_eq!(tuple.get::<usize>(), Some(&14));
+
+    let tuple = meta_tuple!(Attacker(1)).call_join(attack);
+    assert_eq!(tuple.get::<DamageDealt>(), None);
+}
+
+#[test]
+#[should_panic]
+pub fn test_call_alias() {
+    let mut tuple = meta_tuple!(Attacker(10), Defender(4));
+    tuple.call(|_: &mut Attacker, _: &Attacker| ());
+}
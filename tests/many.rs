@@ -0,0 +1,95 @@
+use meta_tuple::{All, MetaAny, MetaTuple, meta_tuple};
+
+#[derive(Debug, PartialEq)]
+struct Config(u32);
+
+#[derive(Debug, PartialEq)]
+struct Player(&'static str);
+
+#[cfg(feature = "alloc")]
+#[derive(meta_tuple::MetaQuery)]
+struct Query<'t> {
+    player: &'t mut Player,
+    power: &'t mut i32,
+}
+
+#[test]
+pub fn test_slice() {
+    let global = meta_tuple!(Config(1), 0i32);
+    let player = meta_tuple!(Player("a"), 1i32);
+    let card = meta_tuple!(2i32, 'c');
+    let layers: &[&dyn MetaAny] = &[&card, &player, &global];
+
+    assert_eq!(MetaTuple::get::<i32>(&layers), Some(&2));
+    assert_eq!(MetaTuple::get::<Config>(&layers), Some(&Config(1)));
+    assert_eq!(layers.count::<i32>(), 3);
+    assert_eq!(
+        layers.get_all::<i32>().copied().collect::<Vec<_>>(),
+        [2, 1, 0]
+    );
+    assert_eq!(
+        layers
+            .query_ref::<(&Player, &char, All<&i32>)>()
+            .map(|(p, c, all)| (p, c, all.count())),
+        Some((&Player("a"), &'c', 3))
+    );
+
+    let mut layers = layers;
+    assert_eq!(MetaTuple::get_mut::<i32>(&mut layers), None);
+
+    let dynamic: &dyn MetaAny = &layers;
+    assert_eq!(dynamic.get::<Player>(), Some(&Player("a")));
+    assert_eq!(dynamic.get_nth::<i32>(2), Some(&0));
+    assert_eq!(dynamic.len(), 6);
+}
+
+#[test]
+pub fn test_array() {
+    let mut layers = [meta_tuple!(1i32, 'a'), meta_tuple!(2i32, 'b')];
+    assert_eq!(layers.get::<char>(), Some(&'a'));
+    assert_eq!(layers.get_nth::<char>(1), Some(&'b'));
+
+    #[cfg(feature = "alloc")]
+    {
+        let (all, c) = layers.query_mut::<(All<&mut i32>, &mut char)>().unwrap();
+        for int in all {
+            *int += 1;
+            *c = 'c';
+        }
+        assert_eq!(layers.get_all::<i32>().copied().collect::<Vec<_>>(), [2, 3]);
+        assert_eq!(layers[0].get::<char>(), Some(&'c'));
+    }
+    #[cfg(not(feature = "alloc"))]
+    for int in layers.get_all_mut::<i32>() {
+        *int += 1;
+    }
+
+    let dynamic: &mut dyn MetaAny = &mut layers;
+    *dynamic.get_nth_mut::<char>(1).unwrap() = 'd';
+    assert_eq!(dynamic.get_mut::<i32>(), Some(&mut 2));
+    assert_eq!(layers[1].get::<char>(), Some(&'d'));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn test_vec() {
+    let mut layers: Vec<Box<dyn MetaAny>> = vec![
+        meta_tuple!(1i32).into_dyn(),
+        meta_tuple!(Player("a"), 2i32).into_dyn(),
+    ];
+    let query = layers.query_mut::<Query>().unwrap();
+    *query.power += 1;
+    query.player.0 = "b";
+    *query.power += 1;
+    assert_eq!(layers.get::<i32>(), Some(&3));
+    assert_eq!(layers.get::<Player>(), Some(&Player("b")));
+
+    let dynamic: &mut dyn MetaAny = &mut layers;
+    for item in dynamic.get_all_mut::<i32>() {
+        *item += 1;
+    }
+    assert_eq!(
+        dynamic.get_all::<i32>().copied().collect::<Vec<_>>(),
+        [4, 3]
+    );
+}
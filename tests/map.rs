@@ -0,0 +1,64 @@
+#![cfg(feature = "alloc")]
+use core::any::TypeId;
+use meta_tuple::{MetaAny, MetaBundle, MetaMap, MetaTuple, meta_tuple};
+
+#[test]
+pub fn test_map() {
+    let mut map = MetaMap::new();
+    assert!(map.is_empty());
+    assert_eq!(map.insert(1i32), None);
+    assert_eq!(map.insert(2i32), Some(1));
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.get::<i32>(), Some(&2));
+
+    *map.entry::<f32>().or_insert_with(|| 1.0) += 1.0;
+    *map.entry::<f32>().or_insert_with(|| 1.0) += 1.0;
+    assert_eq!(map.get::<f32>(), Some(&3.0));
+    map.entry::<f32>().and_modify(|x| *x = 0.5).or_default();
+    assert_eq!(map.get::<f32>(), Some(&0.5));
+
+    map.get_or_default::<String>().push_str("hello");
+    map.get_or_default::<String>().push_str(" world");
+    assert_eq!(map.get::<String>().map(|x| x.as_str()), Some("hello world"));
+
+    map.extend((3i32, 'c', 4u8));
+    assert_eq!(map.len(), 5);
+    assert_eq!(map.get::<i32>(), Some(&3));
+
+    assert_eq!(map.remove::<f32>(), Some(0.5));
+    assert_eq!(map.remove::<f32>(), None);
+    assert!(!map.contains::<f32>());
+    assert_eq!(map.get::<String>().map(|x| x.as_str()), Some("hello world"));
+    assert_eq!(map.get::<char>(), Some(&'c'));
+    assert_eq!(map.get::<u8>(), Some(&4));
+    assert_eq!(map.len(), 4);
+
+    let tuple = meta_tuple!(1u64).join_dyn_ref(&map);
+    assert_eq!(tuple.get::<u8>(), Some(&4));
+    assert_eq!(tuple.get::<u64>(), Some(&1));
+
+    let dynamic: &mut dyn MetaAny = &mut map;
+    *dynamic.get_mut::<char>().unwrap() = 'd';
+    assert_eq!(map.query_ref::<(&char, &i32)>(), Some((&'d', &3)));
+}
+
+#[test]
+pub fn test_erased_lookup() {
+    let mut map = MetaMap::new();
+    map.extend((1i32, 2u8, 3u16, 'c'));
+    map.remove::<i32>();
+    assert_eq!(map.find_field(TypeId::of::<char>(), 0), Ok(0));
+    assert_eq!(map.find_field(TypeId::of::<char>(), 1), Err(1));
+    assert_eq!(map.find_field(TypeId::of::<i32>(), 0), Err(0));
+
+    let mut tuple = meta_tuple!(4u8).join_dyn_mut(&mut map);
+    assert_eq!(tuple.get_all::<u8>().collect::<Vec<_>>(), [&4, &2]);
+    assert_eq!(tuple.count::<char>(), 1);
+    let (a, b) = tuple.query_mut::<(&mut u16, &mut char)>().unwrap();
+    *a += 1;
+    *b = 'd';
+    for x in tuple.get_all_mut::<u8>() {
+        *x += 1;
+    }
+    assert_eq!(map.query_ref::<(&u8, &u16, &char)>(), Some((&3, &4, &'d')));
+}
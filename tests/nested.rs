@@ -0,0 +1,99 @@
+use meta_tuple::{Access, All, MetaAny, MetaError, MetaQuery, MetaTuple, meta_tuple};
+
+#[derive(Debug, PartialEq)]
+struct Health(i32);
+
+#[derive(Debug, PartialEq)]
+struct Armor(i32);
+
+#[derive(Debug, MetaQuery)]
+struct Defense<'t> {
+    health: &'t mut Health,
+    armor: &'t Armor,
+}
+
+#[derive(Debug, MetaQuery)]
+struct Attack<'t> {
+    defense: Defense<'t>,
+    power: &'t i32,
+    bonus: (&'t f32, Option<&'t u8>),
+}
+
+#[derive(Debug, MetaQuery)]
+struct Reader<'t> {
+    armor: &'t Armor,
+    power: &'t i32,
+}
+
+fn access<Q: MetaQuery>() -> Vec<Access> {
+    let mut result = Vec::new();
+    Q::access(&mut |access| result.push(access));
+    result
+}
+
+#[test]
+pub fn test_nested() {
+    let mut tuple = meta_tuple!(Health(10), Armor(2), 3i32, 0.5f32);
+    let (defense, float) = tuple.query_mut::<(Defense, &mut f32)>().unwrap();
+    defense.health.0 -= defense.armor.0;
+    *float += 1.0;
+    defense.health.0 -= 1;
+    assert_eq!(tuple.get::<Health>(), Some(&Health(7)));
+
+    let attack = tuple.query_mut::<Attack>().unwrap();
+    attack.defense.health.0 -= *attack.power;
+    assert_eq!(attack.bonus, (&1.5, None));
+    assert_eq!(tuple.get::<Health>(), Some(&Health(4)));
+
+    let ((armor, power), reader) = tuple.query_ref::<((&Armor, &i32), Reader)>().unwrap();
+    assert_eq!((armor, power), (reader.armor, reader.power));
+    assert!(tuple.query_ref::<Attack>().is_none());
+
+    let dynamic: &mut dyn MetaAny = &mut tuple;
+    let (defense, power) = dynamic.query_mut::<(Defense, &i32)>().unwrap();
+    defense.health.0 += power;
+    assert_eq!(dynamic.get::<Health>(), Some(&Health(7)));
+}
+
+#[test]
+pub fn test_access() {
+    assert_eq!(
+        access::<Attack>(),
+        [
+            Access::of::<&mut Health, Health>(true),
+            Access::of::<&Armor, Armor>(false),
+            Access::of::<&i32, i32>(false),
+            Access::of::<&f32, f32>(false),
+            Access::of::<Option<&u8>, u8>(false),
+        ]
+    );
+    #[cfg(feature = "alloc")]
+    assert_eq!(
+        access::<All<&mut i32>>()[0].type_id,
+        core::any::TypeId::of::<i32>()
+    );
+    assert!(Access::of::<&i32, i32>(false).conflicts(&Access::of::<&mut i32, i32>(true)));
+    assert!(!Access::of::<&i32, i32>(false).conflicts(&Access::of::<&i32, i32>(false)));
+}
+
+#[test]
+pub fn test_validate() {
+    assert!(<(&i32, &i32)>::validate());
+    assert!(<(Reader, &Armor, All<&i32>)>::validate());
+    assert!(<(Defense, &mut i32)>::validate());
+    assert!(!<(&i32, &mut i32)>::validate());
+    assert!(!<(Defense, &Health)>::validate());
+    assert!(!<(Reader, (&f32, Option<&mut Armor>))>::validate());
+    assert!(!<(Attack, &mut i32)>::validate());
+
+    let mut tuple = meta_tuple!(Health(10), Armor(2), 3i32);
+    assert_eq!(
+        tuple
+            .try_query_mut::<(Reader, Defense, &mut i32)>()
+            .unwrap_err(),
+        MetaError::Aliased {
+            a: "&i32",
+            b: "&mut i32",
+        }
+    );
+}
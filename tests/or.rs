@@ -0,0 +1,99 @@
+use meta_tuple::{AnyOf, MetaAny, MetaError, MetaQuery, MetaTuple, Or, meta_tuple};
+
+#[derive(Debug, PartialEq)]
+struct Single(u32);
+
+#[derive(Debug, PartialEq)]
+struct Area(u32);
+
+#[derive(Debug, PartialEq)]
+struct Line(u32);
+
+#[derive(Debug, MetaQuery)]
+struct Cast<'t> {
+    target: Or<&'t mut Single, &'t mut Area>,
+    power: &'t i32,
+}
+
+#[test]
+pub fn test_or() {
+    let mut single = meta_tuple!(Single(1), 2i32);
+    let area = meta_tuple!(Area(3), 2i32);
+    assert_eq!(
+        single.query_ref::<Or<&Single, &Area>>(),
+        Some(Or::Left(&Single(1)))
+    );
+    assert_eq!(
+        area.query_ref::<Or<&Single, &Area>>(),
+        Some(Or::Right(&Area(3)))
+    );
+    assert_eq!(area.query_ref::<Or<&Single, &Line>>(), None);
+
+    let cast = single.query_mut::<Cast>().unwrap();
+    match cast.target {
+        Or::Left(single) => single.0 += *cast.power as u32,
+        Or::Right(area) => area.0 += 1,
+    }
+    assert_eq!(single.get::<Single>(), Some(&Single(3)));
+
+    let dynamic: &mut dyn MetaAny = &mut single;
+    let (target, int) = dynamic
+        .query_mut::<(Or<&mut Area, &mut Single>, &mut i32)>()
+        .unwrap();
+    let Or::Right(single) = target else { panic!() };
+    single.0 += 1;
+    *int += 1;
+    assert_eq!(dynamic.get::<Single>(), Some(&Single(4)));
+    assert_eq!(dynamic.get::<i32>(), Some(&3));
+}
+
+#[test]
+pub fn test_any_of() {
+    let line = meta_tuple!(Line(1), Area(2));
+    let any = line.query_ref::<AnyOf<(&Single, &Line, &Area)>>().unwrap();
+    assert_eq!(any.index(), Some(1));
+    assert_eq!(any, AnyOf((None, Some(&Line(1)), None)));
+    assert!(line.query_ref::<AnyOf<(&Single, &u8)>>().is_none());
+
+    let mut area = meta_tuple!(Area(1), 'c');
+    let dynamic: &mut dyn MetaAny = &mut area;
+    let (AnyOf((single, area)), c) = dynamic
+        .query_mut::<(AnyOf<(&mut Single, &mut Area)>, &mut char)>()
+        .unwrap();
+    assert!(single.is_none());
+    area.unwrap().0 += 1;
+    *c = 'd';
+    assert_eq!(dynamic.get::<Area>(), Some(&Area(2)));
+    assert_eq!(dynamic.get::<char>(), Some(&'d'));
+}
+
+#[test]
+pub fn test_validate() {
+    assert!(<Or<&mut i32, &i32>>::validate());
+    assert!(<(Or<&mut Single, &Area>, &Area)>::validate());
+    assert!(!<(Or<&mut Single, &Area>, &Single)>::validate());
+    assert!(!<Or<&Single, (&mut i32, &i32)>>::validate());
+    assert!(!<(AnyOf<(&Single, &mut Area)>, &mut Area)>::validate());
+
+    #[cfg(feature = "alloc")]
+    {
+        assert!(<AnyOf<(&mut i32, &i32, meta_tuple::All<&mut i32>)>>::validate());
+        let mut tuple = meta_tuple!(1i32);
+        assert_eq!(
+            tuple.try_query_mut::<Cast>().unwrap_err(),
+            MetaError::Many(vec![
+                MetaError::Missing {
+                    type_name: "or::Single"
+                },
+                MetaError::Missing {
+                    type_name: "or::Area"
+                },
+            ])
+        );
+    }
+    let mut tuple = meta_tuple!(Area(1));
+    assert_eq!(
+        tuple.try_query_mut::<Cast>().unwrap_err(),
+        MetaError::Missing { type_name: "i32" }
+    );
+}
@@ -0,0 +1,85 @@
+use meta_tuple::{
+    Cloned, Copied, Has, MetaAny, MetaError, MetaQuery, MetaTuple, OrDefault, Without, meta_tuple,
+};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Name(String);
+
+#[derive(Debug, PartialEq)]
+struct Frozen;
+
+#[derive(Debug, PartialEq)]
+struct Boss;
+
+#[derive(Debug, MetaQuery)]
+struct Movable<'t> {
+    name: Cloned<Name>,
+    speed: OrDefault<f32>,
+    position: &'t mut i32,
+    _boss: Has<Boss>,
+    _frozen: Without<Frozen>,
+}
+
+#[test]
+pub fn test_owned() {
+    let mut tuple = meta_tuple!(Name(String::from("a")), 1i32, 'c');
+    let (mut name, Copied(int), Copied(c)) = tuple
+        .query_ref::<(Cloned<Name>, Copied<i32>, Copied<char>)>()
+        .unwrap();
+    name.0.0.push('b');
+    assert_eq!((int, c), (1, 'c'));
+    assert_eq!(tuple.get::<Name>(), Some(&Name(String::from("a"))));
+    assert!(tuple.query_ref::<Copied<u8>>().is_none());
+
+    let (byte, int) = tuple
+        .query_ref::<(OrDefault<u8>, OrDefault<i32>)>()
+        .unwrap();
+    assert_eq!((*byte, *int), (0, 1));
+
+    assert!(!<(&mut i32, Copied<i32>)>::validate());
+    let (int, OrDefault(byte)) = tuple.query_mut::<(&mut i32, OrDefault<u8>)>().unwrap();
+    *int += byte as i32 + 1;
+    assert_eq!(tuple.get::<i32>(), Some(&2));
+
+    let dynamic: &dyn MetaAny = &tuple;
+    assert_eq!(
+        dynamic.query_ref::<(Cloned<Name>, OrDefault<Name>, OrDefault<u8>)>(),
+        Some((
+            Cloned(Name(String::from("a"))),
+            OrDefault(Name(String::from("a"))),
+            OrDefault(0)
+        ))
+    );
+}
+
+#[test]
+pub fn test_filter() {
+    let mut boss = meta_tuple!(Boss, Name::default(), 1i32, 2.0f32);
+    let movable = boss.query_mut::<Movable>().unwrap();
+    *movable.position += *movable.speed as i32;
+    assert_eq!(*movable.name, Name::default());
+    assert_eq!(boss.get::<i32>(), Some(&3));
+
+    let mut frozen = meta_tuple!(#boss, Frozen);
+    assert!(frozen.query_mut::<Movable>().is_none());
+    assert_eq!(
+        frozen.try_query_mut::<Movable>().unwrap_err(),
+        MetaError::Unexpected {
+            type_name: "params::Frozen"
+        }
+    );
+    assert!(frozen.query_ref::<(Has<Boss>, Has<Frozen>)>().is_some());
+    assert!(frozen.query_ref::<Without<Boss>>().is_none());
+
+    let mut minion = meta_tuple!(Name::default(), 1i32);
+    assert_eq!(
+        minion.try_query_mut::<Movable>().unwrap_err(),
+        MetaError::Missing {
+            type_name: "params::Boss"
+        }
+    );
+    let dynamic: &mut dyn MetaAny = &mut minion;
+    let (int, _) = dynamic.query_mut::<(&mut i32, Without<Boss>)>().unwrap();
+    *int += 1;
+    assert!(<(Has<i32>, Without<i32>, &mut i32)>::validate());
+}
@@ -0,0 +1,87 @@
+use meta_tuple::{MetaItem, MetaStage, MetaTuple, meta_tuple};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Attacker(pub usize);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Defender(pub usize);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Healer;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DamageDealt(pub usize);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Healed(pub usize);
+
+pub struct Attack;
+
+impl MetaStage for Attack {
+    type Out = MetaItem<DamageDealt>;
+
+    fn run<T: MetaTuple + ?Sized>(&self, input: &T) -> Self::Out {
+        let attacker = input.get::<Attacker>().unwrap();
+        let defender = input.get::<Defender>().unwrap();
+        MetaItem(DamageDealt(attacker.0.saturating_sub(defender.0)))
+    }
+}
+
+pub struct Lifesteal;
+
+impl MetaStage for Lifesteal {
+    type Out = MetaItem<Healed>;
+
+    fn run<T: MetaTuple + ?Sized>(&self, input: &T) -> Self::Out {
+        let damage = input.get::<DamageDealt>().map(|x| x.0).unwrap_or(0);
+        MetaItem(Healed(damage / 2))
+    }
+}
+
+pub struct Double;
+
+impl MetaStage for Double {
+    type Out = MetaItem<usize>;
+
+    fn run<T: MetaTuple + ?Sized>(&self, input: &T) -> Self::Out {
+        MetaItem(input.get::<usize>().unwrap() * 2)
+    }
+}
+
+#[test]
+pub fn test_pipeline() {
+    let input = meta_tuple!(Attacker(10), Defender(4));
+
+    let out = Attack.then(Lifesteal).run(&input);
+    assert_eq!(out.get::<DamageDealt>(), Some(&DamageDealt(6)));
+    assert_eq!(out.get::<Healed>(), Some(&Healed(3)));
+
+    let out = Attack.and(Lifesteal).run(&input);
+    assert_eq!(out.get::<DamageDealt>(), Some(&DamageDealt(6)));
+    assert_eq!(out.get::<Healed>(), Some(&Healed(0)));
+
+    let card = Attack.then(Lifesteal.when::<Healer>());
+    assert_eq!(card.run(&input).get::<Healed>(), None);
+    let out = card.run(&(&input).join(Healer));
+    assert_eq!(out.get::<Healed>(), Some(&Healed(3)));
+
+    let out = Attack
+        .add_mapped::<DamageDealt, DamageDealt>(|x| DamageDealt(x.0 * 3))
+        .run(&input);
+    assert_eq!(out.get::<DamageDealt>(), Some(&DamageDealt(18)));
+    assert_eq!(out.get_nth::<DamageDealt>(1), Some(&DamageDealt(6)));
+    assert_eq!(out.count::<DamageDealt>(), 2);
+    let out = Attack
+        .add_mapped::<Healer, Healed>(|_| Healed(1))
+        .run(&input);
+    assert_eq!(out.get::<Healed>(), None);
+
+    let out = Double.repeat(3).run(&meta_tuple!(1usize));
+    assert_eq!(out.get::<usize>(), Some(&8));
+    let out = Double.repeat(0).run(&meta_tuple!(1usize));
+    assert_eq!(out.get::<usize>(), None);
+
+    let card = &Attack;
+    let out = card.then(card).run(&input);
+    assert_eq!(out.count::<DamageDealt>(), 2);
+}
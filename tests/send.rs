@@ -0,0 +1,69 @@
+use meta_tuple::{MetaAny, MetaTuple, impl_meta_any, meta_tuple};
+use std::thread;
+
+mod ctx {
+    use meta_tuple::MetaAny;
+
+    pub trait Metadata: MetaAny {}
+
+    impl<T: MetaAny> Metadata for T {}
+}
+
+impl_meta_any!(ctx::Metadata + Send);
+
+pub trait Context<P>: MetaAny {
+    fn param(&self) -> Option<&P>;
+}
+
+impl<P: 'static, T: MetaAny> Context<P> for T {
+    fn param(&self) -> Option<&P> {
+        None
+    }
+}
+
+impl_meta_any!([P: 'static] dyn Context<P> + Send + Sync);
+
+#[test]
+pub fn test_send() {
+    let mut boxed: Box<dyn MetaAny + Send> = Box::new(meta_tuple!(1i32, 'c'));
+    let boxed = thread::spawn(move || {
+        *boxed.get_mut::<i32>().unwrap() += 1;
+        let (int, c) = boxed.query_mut::<(&mut i32, &mut char)>().unwrap();
+        *int += 1;
+        *c = 'd';
+        boxed
+    })
+    .join()
+    .unwrap();
+    assert_eq!(boxed.query_ref::<(&i32, &char)>(), Some((&3, &'d')));
+
+    let shared: &(dyn MetaAny + Send + Sync) = &meta_tuple!(1i32, 2.0f32);
+    thread::scope(|scope| {
+        scope.spawn(|| assert_eq!(shared.get::<i32>(), Some(&1)));
+        scope.spawn(|| assert_eq!(shared.get::<f32>(), Some(&2.0)));
+    });
+}
+
+#[test]
+pub fn test_subtrait() {
+    let mut tuple = meta_tuple!(1i32, 'c');
+    let metadata: &mut (dyn ctx::Metadata + Send) = &mut tuple;
+    let ptr = unsafe { <dyn ctx::Metadata + Send>::get_ptr::<i32>(metadata) }.unwrap();
+    unsafe { *ptr += 1 };
+    let mut float = 1.0f32;
+    let mut joined = metadata.join(2u8).join_mut(&mut float);
+    let (int, byte, float) = joined.query_mut::<(&mut i32, &mut u8, &mut f32)>().unwrap();
+    *int += 1;
+    *byte += 1;
+    *float += 1.0;
+    assert_eq!(
+        joined.query_ref::<(&i32, &u8, &f32)>(),
+        Some((&3, &3, &2.0))
+    );
+
+    let context: &mut (dyn Context<String> + Send + Sync) = &mut tuple;
+    assert_eq!(context.param(), None::<&String>);
+    assert_eq!(context.query_ref::<(&i32, &char)>(), Some((&3, &'c')));
+    assert_eq!(context.join_ref(&"a").get::<&str>(), Some(&"a"));
+    assert_eq!(context.join_tuple(meta_tuple!(1u8)).count::<u8>(), 1);
+}
@@ -0,0 +1,133 @@
+#![cfg(feature = "serde")]
+use meta_tuple::{
+    MetaAny, MetaRegistry, MetaTuple, MetaVec, Unregistered, impl_meta_any, meta_tuple,
+    meta_tuple_type,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Attacker {
+    pub name: String,
+    pub atk: u32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Defender {
+    pub def: u32,
+}
+
+#[derive(Debug, MetaTuple)]
+pub struct Context {
+    pub attacker: Attacker,
+    pub defender: Defender,
+}
+
+pub trait Dyn: MetaAny {}
+
+impl<T: MetaAny> Dyn for T {}
+
+impl_meta_any!(Dyn);
+
+fn registry() -> MetaRegistry {
+    let mut registry = MetaRegistry::new();
+    registry
+        .register::<Attacker>("game::Attacker")
+        .register::<Defender>("game::Defender")
+        .register::<u32>("u32");
+    registry
+}
+
+fn attacker() -> Attacker {
+    Attacker {
+        name: "Alice".to_owned(),
+        atk: 4,
+    }
+}
+
+#[test]
+pub fn test_serialize() {
+    let mut registry = registry();
+    let expected = r#"{"game::Attacker":{"name":"Alice","atk":4},"game::Defender":{"def":2}}"#;
+
+    let tuple = meta_tuple!(attacker(), Defender { def: 2 });
+    assert_eq!(
+        serde_json::to_string(&registry.serialize(&tuple)).unwrap(),
+        expected
+    );
+
+    let context = Context {
+        attacker: attacker(),
+        defender: Defender { def: 2 },
+    };
+    assert_eq!(
+        serde_json::to_string(&registry.serialize(&context)).unwrap(),
+        expected
+    );
+
+    let boxed: Box<dyn Dyn> = Box::new(context);
+    assert_eq!(
+        serde_json::to_string(&registry.serialize(&*boxed)).unwrap(),
+        expected
+    );
+
+    let tuple = meta_tuple!(attacker(), 'c');
+    assert!(serde_json::to_string(&registry.serialize(&tuple)).is_err());
+    registry.set_policy(Unregistered::Skip);
+    assert_eq!(
+        serde_json::to_string(&registry.serialize(&tuple)).unwrap(),
+        r#"{"game::Attacker":{"name":"Alice","atk":4}}"#
+    );
+
+    let tuple = meta_tuple!(Defender { def: 1 }, attacker(), Defender { def: 2 });
+    assert_eq!(
+        serde_json::to_string(&registry.serialize(&tuple)).unwrap(),
+        r#"{"game::Defender":{"def":1},"game::Attacker":{"name":"Alice","atk":4}}"#
+    );
+}
+
+#[test]
+pub fn test_deserialize() {
+    let mut registry = registry();
+    let json = r#"{"u32":1,"game::Defender":{"def":2},"game::Attacker":{"name":"Alice","atk":4}}"#;
+
+    let vec: MetaVec = registry
+        .deserialize(&mut serde_json::Deserializer::from_str(json))
+        .unwrap();
+    assert_eq!(vec.len(), 3);
+    assert_eq!(vec.get::<u32>(), Some(&1));
+    assert_eq!(vec.get::<Attacker>(), Some(&attacker()));
+    let mut names = Vec::new();
+    vec.visit(&mut |_, name| names.push(name));
+    assert_eq!(names[1], std::any::type_name::<Defender>());
+
+    let tuple: meta_tuple_type!(Attacker, Defender) = registry
+        .deserialize_static(&mut serde_json::Deserializer::from_str(json))
+        .unwrap();
+    assert_eq!(tuple.get::<Defender>(), Some(&Defender { def: 2 }));
+    assert_eq!(tuple.get::<Attacker>(), Some(&attacker()));
+
+    let missing = registry.deserialize_static::<meta_tuple_type!(Attacker, char), _>(
+        &mut serde_json::Deserializer::from_str(json),
+    );
+    assert!(missing.unwrap_err().to_string().contains("char"));
+
+    let json = r#"{"unknown":[1, 2],"u32":1}"#;
+    assert!(
+        registry
+            .deserialize(&mut serde_json::Deserializer::from_str(json))
+            .is_err()
+    );
+    registry.set_policy(Unregistered::Skip);
+    let vec = registry
+        .deserialize(&mut serde_json::Deserializer::from_str(json))
+        .unwrap();
+    assert_eq!(vec.len(), 1);
+    assert_eq!(registry.name_of::<Defender>(), Some("game::Defender"));
+}
+
+#[test]
+#[should_panic]
+pub fn test_name_conflict() {
+    let mut registry = registry();
+    registry.register::<i32>("u32");
+}
@@ -0,0 +1,233 @@
+#![cfg(feature = "alloc")]
+use meta_tuple::{MetaAny, MetaQuery, MetaTuple, meta_tuple};
+
+#[derive(Debug, PartialEq)]
+struct Hand(Vec<u32>);
+
+#[derive(Debug, PartialEq)]
+struct Deck(Vec<u32>);
+
+#[derive(Debug, PartialEq)]
+struct Score(u32);
+
+#[derive(Debug, PartialEq)]
+struct Turn(u32);
+
+#[derive(Debug, MetaTuple)]
+struct Game {
+    hand: Hand,
+    deck: Deck,
+    score: Score,
+    turn: Turn,
+}
+
+#[derive(Debug, MetaQuery)]
+struct Draw<'t> {
+    hand: &'t mut Hand,
+    deck: &'t mut Deck,
+}
+
+fn score(input: &mut impl MetaTuple) {
+    let (score, turn) = input.query_mut::<(&mut Score, &Turn)>().unwrap();
+    score.0 += turn.0;
+}
+
+#[test]
+pub fn test_split() {
+    let mut tuple = meta_tuple!(Hand(vec![]), Deck(vec![1, 2]), Score(0), Turn(3));
+    let ((hand, deck), mut rest) = tuple.split_query_mut::<(&mut Hand, &mut Deck)>().unwrap();
+    hand.0.push(deck.0.pop().unwrap());
+    assert_eq!(rest.get::<Hand>(), None);
+    assert!(rest.get_mut::<Deck>().is_none());
+    score(&mut rest);
+    hand.0.push(deck.0.pop().unwrap());
+    assert_eq!(rest.get::<Score>(), Some(&Score(3)));
+    assert_eq!(tuple.get::<Hand>(), Some(&Hand(vec![2, 1])));
+    assert_eq!(tuple.get::<Score>(), Some(&Score(3)));
+
+    let mut game = Game {
+        hand: Hand(vec![]),
+        deck: Deck(vec![1]),
+        score: Score(0),
+        turn: Turn(2),
+    };
+    let (draw, mut rest) = game.split_query_mut::<Draw>().unwrap();
+    draw.hand.0.append(&mut draw.deck.0);
+    score(&mut rest);
+    assert_eq!(rest.count::<Hand>(), 0);
+    assert_eq!(game.hand, Hand(vec![1]));
+    assert_eq!(game.score, Score(2));
+    assert!(game.split_query_mut::<(&mut Hand, &u8)>().is_none());
+}
+
+#[test]
+pub fn test_reads() {
+    let mut tuple = meta_tuple!(Hand(vec![1]), Deck(vec![]), Score(0));
+    let ((hand, deck), mut rest) = tuple.split_query_mut::<(&mut Hand, &Deck)>().unwrap();
+    assert_eq!(rest.get::<Deck>(), Some(deck));
+    assert!(rest.get_mut::<Deck>().is_none());
+    assert!(rest.query_mut::<(&mut Score, &Deck)>().is_some());
+    assert!(rest.query_mut::<&mut Deck>().is_none());
+    rest.get_mut::<Score>().unwrap().0 += 1;
+    hand.0.clear();
+
+    let (_, rest) = tuple.split_query_mut::<&Deck>().unwrap();
+    let rest = rest.hide_reads();
+    assert!(rest.get::<Deck>().is_none());
+    assert_eq!(rest.get::<Score>(), Some(&Score(1)));
+}
+
+#[test]
+pub fn test_all() {
+    let mut tuple = meta_tuple!(1i32, Score(0), 2i32, Turn(1));
+    let (turn, mut rest) = tuple.split_query_mut::<&mut Turn>().unwrap();
+    for int in rest.get_all_mut::<i32>() {
+        *int += 1;
+    }
+    turn.0 += 1;
+    assert_eq!(rest.get_all::<i32>().collect::<Vec<_>>(), [&2, &3]);
+    assert_eq!(rest.get_nth::<i32>(1), Some(&3));
+    assert_eq!(rest.count::<Turn>(), 0);
+    assert_eq!(tuple.get::<Turn>(), Some(&Turn(2)));
+}
+
+#[test]
+pub fn test_dyn() {
+    let mut tuple = meta_tuple!(Hand(vec![]), Deck(vec![1]), Score(0), Turn(1));
+    let dynamic: &mut dyn MetaAny = &mut tuple;
+    let (draw, mut rest) = dynamic.split_query_mut::<Draw>().unwrap();
+    draw.hand.0.append(&mut draw.deck.0);
+    let remainder: &mut dyn MetaAny = &mut rest;
+    let (score, turn) = remainder.query_mut::<(&mut Score, &Turn)>().unwrap();
+    score.0 += turn.0;
+    assert!(remainder.get::<Deck>().is_none());
+    assert!(draw.deck.0.is_empty());
+    assert_eq!(dynamic.get::<Score>(), Some(&Score(1)));
+    assert_eq!(dynamic.get::<Hand>(), Some(&Hand(vec![1])));
+}
+
+#[test]
+#[should_panic]
+pub fn test_aliased() {
+    let mut tuple = meta_tuple!(Score(0));
+    let _ = tuple.split_query_mut::<(&mut Score, &Score)>();
+}
+
+#[test]
+pub fn test_nested() {
+    let mut tuple = meta_tuple!(1i32).join_tuple(Some(2u8)).join(3u16);
+    let (byte, mut rest) = tuple.split_query_mut::<&mut u8>().unwrap();
+    *byte += 1;
+    *rest.get_mut::<u16>().unwrap() += 1;
+    *byte += 1;
+    assert!(rest.get::<u8>().is_none());
+    *byte += 1;
+    assert_eq!(tuple.get::<u8>(), Some(&5));
+
+    let mut tuple = meta_tuple!(#Some(Box::new(1u8)), #Some(2i32), 3u16);
+    let (byte, mut rest) = tuple.split_query_mut::<&mut Box<u8>>().unwrap();
+    **byte += 1;
+    *rest.get_mut::<i32>().unwrap() += 1;
+    *rest.get_mut::<u16>().unwrap() += 1;
+    **byte += 1;
+    assert_eq!(tuple.get::<Box<u8>>(), Some(&Box::new(3)));
+}
+
+#[test]
+pub fn test_nested_dyn() {
+    let mut inner = meta_tuple!(Score(0), Turn(1));
+    let mut tuple = meta_tuple!(1i32).join_dyn_mut(&mut inner);
+    let (score, mut rest) = tuple.split_query_mut::<&mut Score>().unwrap();
+    score.0 += 1;
+    *rest.get_mut::<i32>().unwrap() += 1;
+    rest.get_mut::<Turn>().unwrap().0 += 1;
+    assert!(rest.get::<Score>().is_none());
+    score.0 += 1;
+    assert_eq!(inner.get::<Score>(), Some(&Score(2)));
+    assert_eq!(inner.get::<Turn>(), Some(&Turn(2)));
+
+    let mut tuple = meta_tuple!(1i32).join_dyn_mut(&mut inner);
+    let (score, mut rest) = tuple.split_query_mut::<&Score>().unwrap();
+    rest.get_mut::<Turn>().unwrap().0 += score.0;
+    assert_eq!(score, &Score(2));
+    assert_eq!(inner.get::<Turn>(), Some(&Turn(4)));
+}
+
+#[test]
+pub fn test_nested_box() {
+    let mut tuple = meta_tuple!(Box::new(Score(0)), #Box::new(meta_tuple!(Turn(1), 2i32)));
+    let (turn, mut rest) = tuple.split_query_mut::<&mut Turn>().unwrap();
+    turn.0 += 1;
+    *rest.get_mut::<i32>().unwrap() += 1;
+    turn.0 += 1;
+    rest.get_mut::<Box<Score>>().unwrap().0 += 1;
+    turn.0 += 1;
+    assert_eq!(tuple.get::<Turn>(), Some(&Turn(4)));
+    assert_eq!(tuple.get::<i32>(), Some(&3));
+}
+
+#[test]
+pub fn test_ref_joined() {
+    let turn = Turn(1);
+    let mut tuple = meta_tuple!(Score(0), &turn, 2i32);
+    let (score, mut rest) = tuple.split_query_mut::<&mut Score>().unwrap();
+    assert_eq!(rest.get::<Turn>(), Some(&Turn(1)));
+    assert!(rest.get_mut::<Turn>().is_none());
+    assert!(rest.query_mut::<(&mut i32, &Turn)>().is_some());
+    *rest.get_mut::<i32>().unwrap() += 1;
+    score.0 += 1;
+    assert_eq!(rest.len(), 2);
+    assert_eq!(tuple.get::<i32>(), Some(&3));
+}
+
+#[test]
+pub fn test_dyn_joined() {
+    let mut tuple = meta_tuple!(
+        Score(0),
+        #Box::new(meta_tuple!(Turn(1), 2i32)) as Box<dyn MetaAny>
+    );
+    let (score, mut rest) = tuple.split_query_mut::<&mut Score>().unwrap();
+    rest.get_mut::<Turn>().unwrap().0 += 1;
+    score.0 += 1;
+    *rest.get_mut::<i32>().unwrap() += 1;
+    score.0 += 1;
+    assert_eq!(rest.len(), 2);
+    assert_eq!(
+        tuple.query_ref::<(&Score, &Turn, &i32)>(),
+        Some((&Score(2), &Turn(2), &3))
+    );
+}
+
+#[test]
+pub fn test_option() {
+    let mut tuple = meta_tuple!(#Some(Box::new(1u8)), #Some(Turn(1)), #None::<i32>, Score(0));
+    let (score, mut rest) = tuple.split_query_mut::<&mut Score>().unwrap();
+    **rest.get_mut::<Box<u8>>().unwrap() += 1;
+    score.0 += 1;
+    rest.get_mut::<Turn>().unwrap().0 += 1;
+    assert!(rest.get::<i32>().is_none());
+    score.0 += 1;
+    assert_eq!(rest.len(), 2);
+    assert_eq!(tuple.get::<Box<u8>>(), Some(&Box::new(2)));
+    assert_eq!(tuple.get::<Score>(), Some(&Score(2)));
+}
+
+#[test]
+pub fn test_visit() {
+    let turn = Turn(1);
+    let mut tuple = meta_tuple!(Score(0), &turn, 1i32, 2u8);
+    let ((score, int), mut rest) = tuple.split_query_mut::<(&mut Score, &i32)>().unwrap();
+    let mut names = Vec::new();
+    rest.visit(&mut |_, name| names.push(name));
+    assert_eq!(names, ["split::Turn", "i32", "u8"]);
+    let mut names = Vec::new();
+    rest.visit_mut(&mut |item, name| {
+        *item.downcast_mut::<u8>().unwrap() += 1;
+        names.push(name)
+    });
+    assert_eq!(names, ["u8"]);
+    score.0 += *int as u32;
+    assert_eq!(rest.len(), 3);
+    assert_eq!(rest.hide_reads().len(), 2);
+    assert_eq!(tuple.query_ref::<(&Score, &u8)>(), Some((&Score(1), &3)));
+}
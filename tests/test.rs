@@ -1,7 +1,8 @@
 use core::fmt::Display;
 
 use meta_tuple::{
-    IntoMetaTuple, MetaAny, MetaItem, MetaQuery, MetaTuple, meta_tuple, meta_tuple_type,
+    GetStatic, IntoMetaTuple, MetaAny, MetaItem, MetaQuery, MetaTuple, Select, meta_tuple,
+    meta_tuple_type,
 };
 
 #[derive(Debug, PartialEq, Eq, MetaItem)]
@@ -19,6 +20,18 @@ struct MyTypeGeneric3<T: Display, const N: usize>(T);
 #[derive(MetaItem)]
 struct MyTypeGeneric4<T: Display, const N: usize>(T);
 
+#[test]
+pub fn test_generic_items() {
+    let tuple = meta_tuple!(
+        #MyTypeGeneric2(1),
+        #MyTypeGeneric3::<_, 2>('c'),
+        #MyTypeGeneric4::<_, 3>("a")
+    );
+    assert_eq!(tuple.get::<MyTypeGeneric2<i32>>().unwrap().0, 1);
+    assert_eq!(tuple.get::<MyTypeGeneric3<char, 2>>().unwrap().0, 'c');
+    assert_eq!(tuple.get::<MyTypeGeneric4<&str, 3>>().unwrap().0, "a");
+}
+
 #[test]
 pub fn test() {
     let _: () = meta_tuple!();
@@ -146,3 +159,171 @@ pub fn test_query() {
         })
     );
 }
+
+fn sum_static<M: Select<i32, I1> + Select<f32, I2>, I1, I2>(input: &M) -> f32 {
+    *input.get_static::<i32>() as f32 + *input.get_static::<f32>()
+}
+
+#[test]
+pub fn test_has() {
+    let mut a = meta_tuple!(1i32, 2.5f32, "hello");
+    assert_eq!(sum_static(&a), 3.5);
+    assert_eq!(a.get_static::<&str>(), &"hello");
+    *a.get_static_mut::<i32>() = 4;
+    assert_eq!(sum_static(&a), 6.5);
+
+    let b = 1u64;
+    let mut c = meta_tuple!(#&mut a, &b, 'c');
+    assert_eq!(sum_static(&c), 6.5);
+    assert_eq!(c.get_static::<u64>(), &1);
+    *c.get_static_mut::<char>() = 'd';
+    assert_eq!(c.get::<char>(), Some(&'d'));
+
+    let mut d = MyTuple {
+        int: 21,
+        string: "Hello".to_owned(),
+        char: 'c',
+        float: 3.0,
+    };
+    assert_eq!(sum_static(&d), 24.0);
+    d.get_static_mut::<String>().push('!');
+    assert_eq!(d.get_static::<String>(), "Hello!");
+
+    let e = meta_tuple!(#MyType, #MyTypeGeneric(1u8));
+    assert_eq!(e.get_static::<MyTypeGeneric<u8>>(), &MyTypeGeneric(1));
+}
+
+#[test]
+pub fn test_take() {
+    let s = String::from("borrowed");
+    let tuple = meta_tuple!(1i32, 2.5f32, &s, vec![1u8]);
+    let (int, rest) = tuple.split::<i32, _>();
+    assert_eq!(int, 1);
+    assert_eq!(rest.get::<i32>(), None);
+    assert_eq!(rest.get::<String>(), Some(&s));
+    let ((float, bytes), rest) = rest.split_query::<(f32, Vec<u8>), _>();
+    assert_eq!(float, 2.5);
+    assert_eq!(bytes, vec![1]);
+    assert_eq!(rest.get::<String>(), Some(&s));
+    assert_eq!(rest.get::<f32>(), None);
+
+    let tuple = MyTuple {
+        int: 21,
+        string: "Hello".to_owned(),
+        char: 'c',
+        float: 3.0,
+    };
+    let (string, rest) = tuple.split::<String, _>();
+    assert_eq!(string, "Hello");
+    assert_eq!(rest.query_ref::<(&i32, &char, &f32)>(), Some((&21, &'c', &3.0)));
+    assert_eq!(rest.get::<String>(), None);
+    let ((), rest) = rest.split_query::<(), _>();
+    assert_eq!(rest.take::<char, _>(), 'c');
+
+    let tuple = meta_tuple!(#MyType, #MyTuple2("a", vec![], 4));
+    let ((ty, a), rest) = tuple.split_query::<(MyType, &str), _>();
+    assert_eq!(ty, MyType);
+    assert_eq!(a, "a");
+    assert_eq!(rest.get::<i32>(), Some(&4));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+pub fn test_get_all() {
+    use meta_tuple::All;
+
+    let mut a = 4i32;
+    let b = 5i32;
+    let mut tuple = meta_tuple!(1i32, 'c', 2i32, &mut a, &b, #MyTuple2("x", vec![], 3));
+    assert_eq!(tuple.count::<i32>(), 5);
+    assert_eq!(tuple.get_all::<i32>().copied().collect::<Vec<_>>(), vec![1, 2, 4, 5, 3]);
+    assert_eq!(tuple.get_nth::<i32>(1), Some(&2));
+    assert_eq!(tuple.get_nth::<i32>(5), None);
+    assert_eq!(tuple.count::<u8>(), 0);
+    for x in tuple.get_all_mut::<i32>() {
+        *x *= 10;
+    }
+    assert_eq!(tuple.get_all::<i32>().copied().collect::<Vec<_>>(), vec![10, 20, 40, 5, 30]);
+    *tuple.get_nth_mut::<i32>(3).unwrap() = 0;
+    assert_eq!(tuple.get_nth::<i32>(3), Some(&5));
+    assert_eq!(tuple.get_nth::<i32>(4), Some(&0));
+
+    let (all, c) = tuple.query_ref::<(All<&i32>, &char)>().unwrap();
+    assert_eq!(all.count(), 5);
+    assert_eq!(c, &'c');
+    assert!(tuple.query_ref::<(All<&mut i32>,)>().is_none());
+    let (all, c) = tuple.query_mut::<(All<&mut i32>, &mut char)>().unwrap();
+    for x in all {
+        *x += 1;
+    }
+    *c = 'd';
+    assert_eq!(tuple.get_all::<i32>().copied().collect::<Vec<_>>(), vec![11, 21, 41, 5, 1]);
+
+    let dynamic: &mut dyn MetaAny = &mut tuple;
+    assert_eq!(dynamic.count::<i32>(), 5);
+    assert_eq!(dynamic.get_nth::<i32>(4), Some(&1));
+    assert_eq!(dynamic.get_all::<i32>().copied().collect::<Vec<_>>(), vec![11, 21, 41, 5, 1]);
+    for x in dynamic.get_all_mut::<i32>() {
+        *x -= 1;
+    }
+    *dynamic.get_nth_mut::<i32>(0).unwrap() = 100;
+    assert_eq!(dynamic.get_nth_mut::<i32>(3), Some(&mut 0));
+    assert_eq!(dynamic.get_all::<i32>().copied().collect::<Vec<_>>(), vec![100, 20, 40, 5, 0]);
+    assert_eq!(
+        <(All<&i32>,)>::query_dyn_ref(dynamic).map(|(x,)| x.count()),
+        Some(5)
+    );
+    assert_eq!(a, 40);
+}
+
+#[test]
+pub fn test_visit() {
+    use std::any::Any;
+    #[cfg(feature = "alloc")]
+    use std::any::TypeId;
+
+    let s = String::from("borrowed");
+    let mut tuple = meta_tuple!(1i32, &s, #Some('c'), #None::<u8>, #MyTuple2("x", vec![2], 3));
+    let mut names = Vec::new();
+    tuple.visit(&mut |_, name| names.push(name));
+    assert_eq!(
+        names,
+        vec!["i32", "alloc::string::String", "char", "&str", "alloc::vec::Vec<u8>", "i32"]
+    );
+    assert_eq!(tuple.len(), 6);
+    assert!(!tuple.is_empty());
+    assert!(meta_tuple!().is_empty());
+    assert!(tuple.contains::<char>());
+    assert!(!tuple.contains::<u8>());
+    #[cfg(feature = "alloc")]
+    assert_eq!(
+        tuple.type_ids().collect::<Vec<_>>(),
+        vec![
+            TypeId::of::<i32>(),
+            TypeId::of::<String>(),
+            TypeId::of::<char>(),
+            TypeId::of::<&str>(),
+            TypeId::of::<Vec<u8>>(),
+            TypeId::of::<i32>(),
+        ]
+    );
+
+    tuple.visit_mut(&mut |item, _| {
+        if let Some(x) = item.downcast_mut::<i32>() {
+            *x += 1;
+        }
+    });
+    assert_eq!(tuple.get_all::<i32>().copied().collect::<Vec<_>>(), vec![2, 4]);
+
+    let dynamic: &mut dyn MetaAny = &mut tuple;
+    let mut count = 0;
+    dynamic.visit_mut(&mut |item: &mut dyn Any, _| {
+        assert!(!item.is::<String>());
+        count += 1;
+    });
+    assert_eq!(count, 5);
+    assert_eq!(dynamic.len(), 6);
+    assert!(dynamic.contains::<String>());
+    #[cfg(feature = "alloc")]
+    assert_eq!(dynamic.type_ids().nth(3), Some(TypeId::of::<&str>()));
+}
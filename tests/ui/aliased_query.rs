@@ -0,0 +1,31 @@
+use meta_tuple::{MetaQuery, MetaTuple};
+
+type Int = i32;
+
+#[derive(MetaQuery)]
+pub struct Duplicated<'t> {
+    pub int: &'t i32,
+    pub int_mut: &'t mut i32,
+}
+
+#[derive(MetaQuery)]
+pub struct Aliased<'t> {
+    pub int: Option<&'t mut i32>,
+    pub alias: &'t Int,
+}
+
+#[derive(MetaQuery)]
+pub struct Shared<'t> {
+    pub a: &'t i32,
+    pub b: Option<&'t i32>,
+}
+
+#[derive(MetaQuery)]
+pub enum Variants<'t> {
+    Read(&'t i32),
+    Write(&'t mut i32),
+    Both(&'t i32, &'t mut i32),
+    Named { a: &'t mut Int, b: &'t i32 },
+}
+
+fn main() {}
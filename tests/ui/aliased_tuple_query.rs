@@ -0,0 +1,27 @@
+use meta_tuple::MetaQuery;
+
+type Int = i32;
+
+#[derive(MetaQuery)]
+pub struct Duplicated<'t> {
+    pub pair: (&'t mut i32, &'t mut i32),
+}
+
+#[derive(MetaQuery)]
+pub struct Nested<'t> {
+    pub int: &'t i32,
+    pub pair: (&'t f32, (Option<&'t mut i32>, &'t u8)),
+}
+
+#[derive(MetaQuery)]
+pub struct Aliased<'t> {
+    pub pair: (&'t mut i32, &'t Int),
+}
+
+#[derive(MetaQuery)]
+pub struct Shared<'t> {
+    pub pair: (&'t i32, &'t i32),
+    pub int: &'t i32,
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use meta_tuple::MetaTuple;
+
+#[derive(MetaTuple)]
+pub struct Combined {
+    #[meta(skip, flatten)]
+    pub int: i32,
+}
+
+#[derive(MetaTuple)]
+pub struct Unknown {
+    #[meta(rename)]
+    pub int: i32,
+}
+
+#[derive(MetaTuple)]
+pub enum Flattened {
+    Int(#[meta(flatten)] (i32,)),
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use meta_tuple::{GetStatic, Select, meta_tuple};
+
+fn damage<M: Select<i32, I>, I>(input: &M) -> i32 {
+    *input.get_static::<i32>()
+}
+
+fn main() {
+    let tuple = meta_tuple!(1.0f32, 'c');
+    damage(&tuple);
+}
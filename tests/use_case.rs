@@ -1,13 +1,15 @@
 use std::any::Any;
 
-use meta_tuple::{MetaAny, MetaItem, MetaTuple, impl_meta_any, meta_tuple, meta_tuple_type};
+use meta_tuple::{
+    GetStatic, MetaAny, MetaItem, MetaTuple, Select, impl_meta_any, meta_tuple, meta_tuple_type,
+};
 
 pub struct Attack;
 pub struct Attacker;
 pub struct Defender;
 
 #[derive(MetaItem)]
-pub struct DamageDealt(usize);
+pub struct DamageDealt(pub usize);
 
 impl Attack {
     pub fn calculate_damage(&self, _: &Attacker, _: &Defender) -> usize {
@@ -44,6 +46,12 @@ impl CardComponent for Attack {
     }
 }
 
+fn attack<M: Select<Attacker, I1> + Select<Defender, I2>, I1, I2>(input: &M) -> usize {
+    let attacker = input.get_static::<Attacker>();
+    let defender = input.get_static::<Defender>();
+    Attack.calculate_damage(attacker, defender)
+}
+
 pub trait CardComponent2 {
     type Out: MetaTuple;
     fn play<T: MetaTuple>(&self, input: &T) -> Self::Out;
@@ -76,3 +84,11 @@ fn metadata() {
     let boxed: Box<dyn Metadata> = Box::new(());
     assert_eq!(boxed.get::<f32>(), None)
 }
+
+#[test]
+fn static_attack() {
+    let input = meta_tuple!(1i32, Attacker, "target", Defender);
+    assert_eq!(attack(&input), 40);
+    let attacker = meta_tuple!(Attacker);
+    assert_eq!(attack(&meta_tuple!(#&attacker, Defender)), 40);
+}
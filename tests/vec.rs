@@ -0,0 +1,63 @@
+#![cfg(feature = "alloc")]
+use meta_tuple::{MetaAny, MetaTuple, MetaVec, meta_tuple};
+
+#[derive(Debug, PartialEq, MetaTuple)]
+pub struct Unit;
+
+#[derive(Debug, PartialEq, MetaTuple)]
+pub struct Stats {
+    pub hp: i32,
+    pub name: String,
+}
+
+fn takes_tuple(input: impl MetaTuple) -> Option<i32> {
+    input.get::<i32>().copied()
+}
+
+#[test]
+pub fn test_vec() {
+    let mut vec = MetaVec::new();
+    assert!(vec.is_empty());
+    for i in 0..3i32 {
+        vec.push(i);
+    }
+    assert_eq!(vec.len(), 3);
+    assert_eq!(vec.get::<i32>(), Some(&0));
+    *vec.get_mut::<i32>().unwrap() = 5;
+    assert_eq!(vec.remove::<i32>(), Some(5));
+    assert_eq!(vec.get::<i32>(), Some(&1));
+
+    let s = String::from("ref");
+    vec.extend(meta_tuple!(2.0f32, &s, 'c'));
+    assert_eq!(vec.len(), 4);
+    assert_eq!(vec.get::<f32>(), Some(&2.0));
+    assert_eq!(vec.get::<String>(), None);
+
+    vec.extend((true, "hello"));
+    vec.extend(Stats {
+        hp: 10,
+        name: "Card".to_owned(),
+    });
+    vec.extend(Unit);
+    assert_eq!(vec.get::<&str>(), Some(&"hello"));
+    assert_eq!(vec.get::<String>(), Some(&"Card".to_owned()));
+    assert_eq!(vec.remove::<u64>(), None);
+
+    vec.retain(|x| !x.is::<i32>());
+    assert_eq!(vec.get::<i32>(), None);
+    assert_eq!(vec.len(), 5);
+
+    let mut other = MetaVec::new();
+    other.push(7i32);
+    other.extend(vec);
+    assert_eq!(other.len(), 6);
+    assert_eq!(other.get::<char>(), Some(&'c'));
+
+    let dynamic: &mut dyn MetaAny = &mut other;
+    assert_eq!(dynamic.get::<bool>(), Some(&true));
+    *dynamic.get_mut::<bool>().unwrap() = false;
+    assert_eq!(dynamic.get::<bool>(), Some(&false));
+
+    assert_eq!(takes_tuple(meta_tuple!(1.0f64).join_tuple(&other)), Some(7));
+    assert_eq!(other.query_ref::<(&i32, &char)>(), Some((&7, &'c')));
+}